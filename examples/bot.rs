@@ -0,0 +1,65 @@
+//! 使用内置求解策略的机器人, 第一个参数为策略名称(默认`frequency`)
+//!
+//! cargo run --bin arena -- --bot "target/debug/examples/bot entropy"
+
+use std::io::{self, BufRead, Write};
+
+use wordle::{
+    buildin_words::{ACCEPTABLE, FINAL},
+    protocol::{BotCommand, BotEvent},
+    solver::{pattern_matrix, Context, StrategyKind},
+    word::Word,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let kind = match std::env::args().nth(1) {
+        Some(name) => name.parse::<StrategyKind>()?,
+        None => StrategyKind::default(),
+    };
+    let strategy = kind.build(2048);
+    let acceptable_set = ACCEPTABLE.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let final_set = FINAL.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let patterns = if kind.uses_patterns() {
+        Some(pattern_matrix(&acceptable_set, &final_set, None)?)
+    } else {
+        None
+    };
+    let mut history: Vec<Word> = vec![];
+    let mut difficult = false;
+
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        match serde_json::from_str::<BotEvent>(&line?)? {
+            BotEvent::Start { hard, .. } => {
+                history.clear();
+                difficult = hard;
+            }
+            BotEvent::Feedback {
+                game_over: true, ..
+            }
+            | BotEvent::Result { .. } => continue,
+            BotEvent::Feedback { guess, letters, .. } => {
+                let mut word = Word::parse(&guess)?;
+                for (letter, feedback) in word.get_mut_letters().iter_mut().zip(letters) {
+                    letter.set_state(feedback.state);
+                }
+                history.push(word);
+            }
+            // 出错时放弃本局
+            BotEvent::Error { .. } => return Ok(()),
+        }
+        let context = Context {
+            history: &history,
+            acceptable_set: &acceptable_set,
+            final_set: &final_set,
+            difficult,
+            patterns: patterns.as_ref(),
+        };
+        if let Some(word) = strategy.next_guess(&context) {
+            let command = BotCommand::Guess { word };
+            writeln!(stdout, "{}", serde_json::to_string(&command)?)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
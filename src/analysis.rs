@@ -0,0 +1,131 @@
+//! 赛后分析
+//!
+//! 对每次猜测计算猜测前后的候选词数, 玩家猜测的期望信息量与最佳猜测的对比,
+//! 以及技巧分(期望信息量占最佳的比例)和运气分(实际信息量减去期望信息量).
+use serde::Serialize;
+
+use crate::{
+    pattern::PatternMatrix,
+    protocol::pattern_string,
+    solver::{Context, Entropy, Strategy},
+    state::LetterState,
+    word::Word,
+    wordle::Wordle,
+};
+
+/// 单次猜测的分析
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GuessAnalysis {
+    pub guess: String,
+    // 反馈, 如`GYRRG`
+    pub pattern: String,
+    pub candidates_before: usize,
+    pub candidates_after: usize,
+    // 玩家猜测的期望信息量(比特)
+    pub expected_info: f64,
+    // 实际获得的信息量(比特)
+    pub actual_info: f64,
+    // 期望信息量最大的猜测
+    pub best_guess: String,
+    pub best_info: f64,
+    // 0-100, 期望信息量占最佳的比例
+    pub skill: u32,
+    // 实际信息量减去期望信息量, 正数表示运气好
+    pub luck: f64,
+}
+
+/// 整局的分析
+#[derive(Debug, Clone, PartialEq, Serialize, Default)]
+pub struct GameAnalysis {
+    pub guesses: Vec<GuessAnalysis>,
+    pub solved: bool,
+    // 各次猜测技巧分的平均值
+    pub skill: u32,
+    // 各次猜测运气分之和
+    pub luck: f64,
+}
+
+/// 分析`history`中的每次猜测, 候选词和最佳猜测按照猜测当时的信息计算,
+/// 给出`patterns`时查表计算信息熵
+pub fn analyze(
+    history: &[Word],
+    acceptable_set: &[String],
+    final_set: &[String],
+    difficult: bool,
+    patterns: Option<&PatternMatrix>,
+) -> GameAnalysis {
+    let mut guesses = vec![];
+    for (index, word) in history.iter().enumerate() {
+        let before = Context {
+            history: &history[..index],
+            acceptable_set,
+            final_set,
+            difficult,
+            patterns,
+        };
+        let after = Context {
+            history: &history[..=index],
+            ..before
+        };
+        let candidates = before.candidates();
+        let partitioner = before.partitioner(&candidates);
+        let guess = word.to_string().to_lowercase();
+        let expected_info = partitioner.entropy(&guess);
+        let best_guess = Entropy.next_guess(&before).unwrap_or_else(|| guess.clone());
+        let best_info = partitioner.entropy(&best_guess).max(expected_info);
+        let candidates_before = candidates.len();
+        let candidates_after = after.candidates().len();
+        let actual_info = if candidates_before == 0 || candidates_after == 0 {
+            0.0
+        } else {
+            (candidates_before as f64 / candidates_after as f64).log2()
+        };
+        let skill = if best_info <= 0.0 {
+            100
+        } else {
+            (expected_info / best_info * 100.0).round() as u32
+        };
+        guesses.push(GuessAnalysis {
+            guess,
+            pattern: pattern_string(word),
+            candidates_before,
+            candidates_after,
+            expected_info,
+            actual_info,
+            best_guess,
+            best_info,
+            skill,
+            luck: actual_info - expected_info,
+        });
+    }
+    let solved = history
+        .last()
+        .is_some_and(|word| word.get_letters().iter().all(|l| l.1 == LetterState::G));
+    let skill = if guesses.is_empty() {
+        0
+    } else {
+        guesses.iter().map(|g| g.skill).sum::<u32>() / guesses.len() as u32
+    };
+    let luck = guesses.iter().map(|g| g.luck).sum();
+    GameAnalysis {
+        guesses,
+        solved,
+        skill,
+        luck,
+    }
+}
+
+impl Wordle {
+    ///
+    /// 分析本局的所有猜测
+    ///
+    pub fn analyze(&self) -> GameAnalysis {
+        analyze(
+            &self.history_words,
+            &self.acceptable_set,
+            &self.final_set,
+            self.opt.difficult,
+            self.patterns.as_deref(),
+        )
+    }
+}
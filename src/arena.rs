@@ -0,0 +1,310 @@
+//! 机器人竞技场
+//!
+//! 以子进程启动多个机器人, 通过JSON-lines协议([`crate::protocol`])与之对局,
+//! 每个机器人使用相同的随机答案序列, 最后输出排行榜.
+use std::{
+    io::{BufRead, BufReader},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+use crate::{
+    command::Opt,
+    error::{Result, WordError},
+    protocol::{write_event, BotCommand, BotEvent, ErrorKind},
+    t,
+    word::Word,
+    wordle::{Wordle, MAX_RETRY_TIMES},
+};
+
+/// 单局结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameOutcome {
+    // 猜对, 包含猜测次数
+    Won(u32),
+    // 次数用尽
+    Lost,
+    // 响应超时
+    Timeout,
+    // 无效命令过多, 进程退出等
+    Forfeit,
+}
+
+/// 单个机器人的统计
+#[derive(Debug, Clone, Serialize)]
+pub struct BotReport {
+    pub name: String,
+    pub games: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    pub average_guesses: f64,
+    // 最差情况, 有失败时为`None`
+    pub worst_case: Option<u32>,
+    pub timeouts: usize,
+    pub forfeits: usize,
+    pub outcomes: Vec<GameOutcome>,
+}
+
+impl BotReport {
+    fn new(name: String, outcomes: Vec<GameOutcome>) -> Self {
+        let guesses = outcomes
+            .iter()
+            .filter_map(|o| match o {
+                GameOutcome::Won(n) => Some(*n),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let games = outcomes.len();
+        let wins = guesses.len();
+        let worst_case = if wins == games {
+            guesses.iter().max().copied()
+        } else {
+            None
+        };
+        BotReport {
+            name,
+            games,
+            wins,
+            win_rate: if games == 0 {
+                0.0
+            } else {
+                wins as f64 / games as f64
+            },
+            average_guesses: if wins == 0 {
+                0.0
+            } else {
+                guesses.iter().sum::<u32>() as f64 / wins as f64
+            },
+            worst_case,
+            timeouts: outcomes
+                .iter()
+                .filter(|o| **o == GameOutcome::Timeout)
+                .count(),
+            forfeits: outcomes
+                .iter()
+                .filter(|o| **o == GameOutcome::Forfeit)
+                .count(),
+            outcomes,
+        }
+    }
+}
+
+/// 竞技场设置
+#[derive(Debug, Clone)]
+pub struct Arena {
+    // 机器人启动命令, 以空白分隔参数
+    pub bots: Vec<String>,
+    // 答案序列
+    pub answers: Vec<String>,
+    pub acceptable_set: Vec<String>,
+    pub final_set: Vec<String>,
+    pub difficult: bool,
+    // 每次猜测的超时时间
+    pub timeout: Duration,
+    // 每局允许的无效命令数
+    pub max_errors: u32,
+}
+
+/// 根据种子从`final_set`中生成答案序列
+pub fn seeded_answers(final_set: &[String], seed: u64, games: usize) -> Vec<String> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    (0..games)
+        .map(|_| final_set[rng.gen_range(0..final_set.len())].clone())
+        .collect()
+}
+
+/// 运行中的机器人进程
+struct BotProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl BotProcess {
+    fn spawn(command: &str) -> Result<Self> {
+        let mut parts = command.split_whitespace();
+        let program = parts
+            .next()
+            .ok_or(WordError::CustomError(t!("error.bot_command").to_string()))?;
+        let mut child = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(BotProcess {
+            child,
+            stdin,
+            lines,
+        })
+    }
+
+    fn send(&mut self, events: &[BotEvent]) -> Result<()> {
+        for event in events {
+            write_event(&mut self.stdin, event)?;
+        }
+        Ok(())
+    }
+
+    fn kill(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl Arena {
+    fn new_wordle(&self, answer: &str) -> Result<Wordle> {
+        Ok(Wordle {
+            final_word: Word::parse(answer)?,
+            acceptable_set: self.acceptable_set.clone(),
+            final_set: self.final_set.clone(),
+            opt: Opt {
+                word: Some(answer.to_string()),
+                difficult: self.difficult,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    /// 与一个机器人进行一局游戏, 返回结果及进程是否可以继续使用(超时或退出后需要重启)
+    fn play(&self, bot: &mut BotProcess, answer: &str) -> Result<(GameOutcome, bool)> {
+        let mut wordle = self.new_wordle(answer)?;
+        let mut errors = 0;
+        // 机器人在两局之间退出时写入失败, 本局判负
+        if bot.send(&[wordle.start_event()]).is_err() {
+            return Ok((GameOutcome::Forfeit, false));
+        }
+        loop {
+            let line = match bot.lines.recv_timeout(self.timeout) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => return Ok((GameOutcome::Timeout, false)),
+                Err(RecvTimeoutError::Disconnected) => return Ok((GameOutcome::Forfeit, false)),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let events = match serde_json::from_str::<BotCommand>(&line) {
+                Ok(BotCommand::Guess { word }) => {
+                    wordle.handle_command(BotCommand::Guess { word })?
+                }
+                Ok(_) => return Ok((GameOutcome::Forfeit, true)),
+                Err(e) => vec![BotEvent::Error {
+                    kind: ErrorKind::BadCommand,
+                    message: e.to_string(),
+                }],
+            };
+            if bot.send(&events).is_err() {
+                return Ok((GameOutcome::Forfeit, false));
+            }
+            for event in events {
+                match event {
+                    BotEvent::Result {
+                        won: true, guesses, ..
+                    } => return Ok((GameOutcome::Won(guesses), true)),
+                    BotEvent::Result { won: false, .. } => return Ok((GameOutcome::Lost, true)),
+                    BotEvent::Error { .. } => {
+                        errors += 1;
+                        if errors > self.max_errors {
+                            return Ok((GameOutcome::Forfeit, true));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// 依次运行所有机器人, 返回按胜率和平均猜测次数排序的排行榜
+    pub fn run(&self) -> Result<Vec<BotReport>> {
+        let mut reports = vec![];
+        for command in self.bots.iter() {
+            let mut outcomes = vec![];
+            let mut bot = None;
+            for answer in self.answers.iter() {
+                // 进程退出或无法启动时, 下一局重新启动. 启动失败只判负这一局, 不影响其他机器人
+                if bot.is_none() {
+                    bot = BotProcess::spawn(command).ok();
+                }
+                let Some(process) = bot.as_mut() else {
+                    outcomes.push(GameOutcome::Forfeit);
+                    continue;
+                };
+                let (outcome, alive) = self.play(process, answer)?;
+                outcomes.push(outcome);
+                if !alive {
+                    if let Some(process) = bot.take() {
+                        process.kill();
+                    }
+                }
+            }
+            if let Some(mut bot) = bot {
+                // 关闭stdin, 机器人应在读到EOF后退出
+                drop(bot.stdin);
+                wait_or_kill(&mut bot.child, self.timeout)?;
+            }
+            reports.push(BotReport::new(command.clone(), outcomes));
+        }
+        reports.sort_by(|a, b| {
+            b.win_rate
+                .total_cmp(&a.win_rate)
+                .then(a.average_guesses.total_cmp(&b.average_guesses))
+        });
+        Ok(reports)
+    }
+}
+
+/// 等待进程退出, 超时后强制结束
+fn wait_or_kill(child: &mut Child, timeout: Duration) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if child.try_wait()?.is_some() {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    child.kill()?;
+    child.wait()?;
+    Ok(())
+}
+
+/// 以表格形式输出排行榜
+pub fn format_leaderboard(reports: &[BotReport]) -> String {
+    let mut out = format!(
+        "{:<4} {:<30} {:>6} {:>8} {:>8} {:>6} {:>8}\n",
+        "rank", "bot", "games", "win%", "avg", "worst", "timeout"
+    );
+    for (rank, report) in reports.iter().enumerate() {
+        out.push_str(&format!(
+            "{:<4} {:<30} {:>6} {:>7.1}% {:>8.3} {:>6} {:>8}\n",
+            rank + 1,
+            report.name,
+            report.games,
+            report.win_rate * 100.0,
+            report.average_guesses,
+            report
+                .worst_case
+                .map(|n| n.to_string())
+                .unwrap_or(format!(">{}", MAX_RETRY_TIMES)),
+            report.timeouts,
+        ));
+    }
+    out
+}
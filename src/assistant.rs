@@ -0,0 +1,78 @@
+//! 助手模式
+//!
+//! 在其他地方(如NYT)玩Wordle时, 记录用户输入的猜测和颜色反馈,
+//! 在不知道答案的情况下给出剩余候选词和推荐的猜测词.
+use crate::{
+    error::{Result, WordError},
+    pattern::Pattern,
+    record::now_millis,
+    solver::Context,
+    t,
+    word::Word,
+    wordle::{Wordle, MAX_RETRY_TIMES},
+};
+
+/// 解析`crane GYXXR`形式的输入
+pub fn parse_feedback_line(line: &str) -> Result<(String, Pattern)> {
+    let mut parts = line.split_whitespace();
+    let (Some(guess), Some(feedback), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(WordError::CustomError(
+            t!("error.feedback_format").to_string(),
+        ));
+    };
+    Ok((guess.to_lowercase(), feedback.parse()?))
+}
+
+impl Wordle {
+    ///
+    /// 记录一次外部游戏的猜测及反馈
+    ///
+    pub fn record_feedback(&mut self, guess: &str, pattern: Pattern) -> Result<()> {
+        if self.is_game_over() || self.history_words.len() >= MAX_RETRY_TIMES as usize {
+            return Err(WordError::CustomError(
+                t!("error.no_guesses_left").to_string(),
+            ));
+        }
+        let word = Word::with_states(guess.to_lowercase(), &pattern.states())?;
+        self.states.current_word = Word::parse(guess.to_lowercase())?;
+        if !self.is_current_word_acceptable() && !self.is_current_word_final() {
+            self.states.current_word = Word::default();
+            return Err(WordError::InValidWord(guess.to_string()));
+        }
+        self.cached_letter_states.extend(word.get_letters().iter());
+        self.history_words.push(word);
+        self.guess_times.push(now_millis());
+        self.states.next_state();
+        if pattern.is_solved() {
+            self.game_over();
+        }
+        Ok(())
+    }
+
+    ///
+    /// 撤销最后一次记录的反馈
+    ///
+    pub fn undo_feedback(&mut self) {
+        self.guess_times.pop();
+        if self.history_words.pop().is_some() {
+            self.states.current_try_times -= 1;
+        }
+        self.game_over = false;
+        self.cached_letter_states = self
+            .history_words
+            .iter()
+            .flat_map(|w| w.get_letters().iter().copied())
+            .collect();
+    }
+
+    ///
+    /// 与所有已记录反馈一致的候选答案
+    ///
+    pub fn remaining_candidates(&self) -> Vec<String> {
+        Context::new(self)
+            .candidates()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+}
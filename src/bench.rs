@@ -0,0 +1,206 @@
+//! 求解策略基准测试
+//!
+//! 使用指定策略对`final_set`中的每个答案(或随机抽样)进行对局, 统计猜测次数分布.
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use rand::{seq::SliceRandom, SeedableRng};
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    error::{Result, WordError},
+    pattern::PatternMatrix,
+    solver::{solve, Context, Strategy},
+    t,
+    wordle::MAX_RETRY_TIMES,
+};
+
+// 超过该次数仍未猜中时放弃
+const GIVE_UP_TIMES: usize = 2 * MAX_RETRY_TIMES as usize;
+
+/// 基准测试的难度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BenchMode {
+    Normal,
+    Hard,
+    #[default]
+    Both,
+}
+
+impl BenchMode {
+    /// 需要运行的困难模式开关
+    pub fn difficulties(&self) -> Vec<bool> {
+        match self {
+            BenchMode::Normal => vec![false],
+            BenchMode::Hard => vec![true],
+            BenchMode::Both => vec![false, true],
+        }
+    }
+}
+
+impl FromStr for BenchMode {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "normal" => Ok(BenchMode::Normal),
+            "hard" => Ok(BenchMode::Hard),
+            "both" => Ok(BenchMode::Both),
+            _ => Err(WordError::CustomError(t!("error.bench_mode", s))),
+        }
+    }
+}
+
+/// 单个答案的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct WordResult {
+    pub answer: String,
+    // 猜中所需次数, 放弃时为`None`
+    pub guesses: Option<usize>,
+    pub path: Vec<String>,
+    pub elapsed_ms: f64,
+}
+
+/// 一种模式下的统计结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub strategy: String,
+    pub hard: bool,
+    pub games: usize,
+    pub average_guesses: f64,
+    // 猜测次数 -> 局数, 放弃的局数不计入
+    pub distribution: BTreeMap<usize, usize>,
+    // 超过`MAX_RETRY_TIMES`次才猜中或放弃的答案
+    pub failures: Vec<WordResult>,
+    // 猜测次数最多(相同时耗时最长)的答案
+    pub slowest: Vec<WordResult>,
+    pub elapsed_ms: f64,
+}
+
+/// 从`final_set`中无放回地抽取`size`个答案
+pub fn sample_answers(final_set: &[String], seed: u64, size: usize) -> Vec<String> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    final_set.choose_multiple(&mut rng, size).cloned().collect()
+}
+
+/// 在所有CPU核心上并行运行基准测试, `patterns`为所有对局共用的反馈矩阵
+pub fn run(
+    strategy: &dyn Strategy,
+    acceptable_set: &[String],
+    final_set: &[String],
+    patterns: Option<&PatternMatrix>,
+    answers: &[String],
+    hard: bool,
+    slowest: usize,
+) -> BenchReport {
+    let context = Context {
+        history: &[],
+        acceptable_set,
+        final_set,
+        difficult: hard,
+        patterns,
+    };
+    let start = Instant::now();
+    let results = answers
+        .par_iter()
+        .map(|answer| {
+            let start = Instant::now();
+            let history = solve(strategy, context, answer, GIVE_UP_TIMES);
+            let path = history.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+            let solved = path.last() == Some(answer);
+            WordResult {
+                answer: answer.clone(),
+                guesses: solved.then_some(path.len()),
+                path,
+                elapsed_ms: as_ms(start.elapsed()),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut distribution = BTreeMap::new();
+    for guesses in results.iter().filter_map(|r| r.guesses) {
+        *distribution.entry(guesses).or_insert(0) += 1;
+    }
+    let solved = distribution.values().sum::<usize>();
+    let total = distribution.iter().map(|(k, v)| k * v).sum::<usize>();
+    let failures = results
+        .iter()
+        .filter(|r| r.guesses.is_none_or(|n| n > MAX_RETRY_TIMES as usize))
+        .cloned()
+        .collect();
+    let mut sorted = results;
+    sorted.sort_by(|a, b| {
+        let a_guesses = a.guesses.unwrap_or(usize::MAX);
+        let b_guesses = b.guesses.unwrap_or(usize::MAX);
+        b_guesses
+            .cmp(&a_guesses)
+            .then(b.elapsed_ms.total_cmp(&a.elapsed_ms))
+    });
+    sorted.truncate(slowest);
+
+    BenchReport {
+        strategy: strategy.name().to_string(),
+        hard,
+        games: answers.len(),
+        average_guesses: if solved == 0 {
+            0.0
+        } else {
+            total as f64 / solved as f64
+        },
+        distribution,
+        failures,
+        slowest: sorted,
+        elapsed_ms: as_ms(start.elapsed()),
+    }
+}
+
+fn as_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// 以表格形式输出结果
+pub fn format_report(report: &BenchReport) -> String {
+    let mut out = format!(
+        "strategy: {}  mode: {}  games: {}  average: {:.4}  time: {:.0}ms\n",
+        report.strategy,
+        if report.hard { "hard" } else { "normal" },
+        report.games,
+        report.average_guesses,
+        report.elapsed_ms,
+    );
+    let max = report.distribution.values().max().copied().unwrap_or(1);
+    for (guesses, count) in report.distribution.iter() {
+        let bar = "#".repeat((count * 40).div_ceil(max));
+        out.push_str(&format!("{:>3} | {:>6} {}\n", guesses, count, bar));
+    }
+    out.push_str(&format!(
+        "failures (> {} guesses): {}\n",
+        MAX_RETRY_TIMES,
+        report.failures.len()
+    ));
+    for failure in report.failures.iter() {
+        out.push_str(&format!(
+            "    {}: {}\n",
+            failure.answer,
+            failure.path.join(" ")
+        ));
+    }
+    out.push_str("slowest words:\n");
+    for result in report.slowest.iter() {
+        out.push_str(&format!(
+            "    {} {:>2} guesses {:>8.2}ms  {}\n",
+            result.answer,
+            result
+                .guesses
+                .map(|n| n.to_string())
+                .unwrap_or("-".to_string()),
+            result.elapsed_ms,
+            result.path.join(" "),
+        ));
+    }
+    out
+}
@@ -0,0 +1,38 @@
+use std::{error::Error, time::Duration};
+
+use structopt::StructOpt;
+use wordle::{
+    arena::{format_leaderboard, seeded_answers, Arena},
+    buildin_words::{ACCEPTABLE, FINAL},
+    command::ArenaOpt,
+    wordle::Wordle,
+};
+
+fn main() -> std::result::Result<(), Box<dyn Error>> {
+    let opt = ArenaOpt::from_args();
+    let acceptable_set = match opt.acceptable_set {
+        Some(ref path) => Wordle::read_input_file(path)?,
+        None => ACCEPTABLE.iter().map(|s| s.to_string()).collect(),
+    };
+    let final_set = match opt.final_set {
+        Some(ref path) => Wordle::read_input_file(path)?,
+        None => FINAL.iter().map(|s| s.to_string()).collect(),
+    };
+
+    let arena = Arena {
+        bots: opt.bots,
+        answers: seeded_answers(&final_set, opt.seed, opt.games),
+        acceptable_set,
+        final_set,
+        difficult: opt.difficult,
+        timeout: Duration::from_millis(opt.timeout),
+        max_errors: opt.max_errors,
+    };
+    let reports = arena.run()?;
+    if opt.json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        print!("{}", format_leaderboard(&reports));
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::error::Error;
+
+use structopt::StructOpt;
+use wordle::{
+    bench::{format_report, run, sample_answers},
+    buildin_words::{ACCEPTABLE, FINAL},
+    command::BenchOpt,
+    solver::pattern_matrix,
+    wordle::Wordle,
+};
+
+fn main() -> std::result::Result<(), Box<dyn Error>> {
+    let opt = BenchOpt::from_args();
+    let acceptable_set = match opt.acceptable_set {
+        Some(ref path) => Wordle::read_input_file(path)?,
+        None => ACCEPTABLE.iter().map(|s| s.to_string()).collect(),
+    };
+    let final_set = match opt.final_set {
+        Some(ref path) => Wordle::read_input_file(path)?,
+        None => FINAL.iter().map(|s| s.to_string()).collect(),
+    };
+    let answers = match opt.sample {
+        Some(size) => sample_answers(&final_set, opt.seed, size),
+        None => final_set.clone(),
+    };
+
+    let patterns = if opt.strategy.uses_patterns() {
+        Some(pattern_matrix(
+            &acceptable_set,
+            &final_set,
+            opt.cache.as_deref(),
+        )?)
+    } else {
+        None
+    };
+    let strategy = opt.strategy.build(opt.seed);
+    let reports = opt
+        .mode
+        .difficulties()
+        .into_iter()
+        .map(|hard| {
+            run(
+                strategy.as_ref(),
+                &acceptable_set,
+                &final_set,
+                patterns.as_ref(),
+                &answers,
+                hard,
+                opt.slowest,
+            )
+        })
+        .collect::<Vec<_>>();
+    if opt.json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in reports.iter() {
+            println!("{}", format_report(report));
+        }
+    }
+    Ok(())
+}
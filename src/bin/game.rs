@@ -1,17 +1,30 @@
-use std::{error::Error, io::BufRead};
+use std::{error::Error, io::BufRead, sync::Arc, time::Duration};
 
 use rand::{Rng, SeedableRng};
 use structopt::StructOpt;
 use wordle::{
     buildin_words::{ACCEPTABLE, FINAL},
+    challenge::Challenge,
+    cmd::run_replay,
     command::Opt,
-    state::Mode,
+    error::WordError,
+    i18n,
+    layout::KeyboardLayout,
+    protocol::Protocol,
+    record::GameRecord,
+    solver::pattern_matrix,
+    state::{no_color, Mode},
+    t,
     word::Word,
-    wordle::Wordle,
+    wordle::{Wordle, WordleStatistic},
 };
 
 fn main() -> std::result::Result<(), Box<dyn Error>> {
-    let opt = Opt::from_args();
+    let mut opt = Opt::from_args();
+    i18n::init(opt.locale);
+    if no_color() {
+        colored::control::set_override(false);
+    }
     let mode = if atty::is(atty::Stream::Stdout) {
         Mode::Interactive
     } else {
@@ -28,13 +41,47 @@ fn main() -> std::result::Result<(), Box<dyn Error>> {
         None => FINAL.iter().map(|s| s.to_string()).collect(),
     };
 
+    // 回放对局记录
+    if let Some(ref path) = opt.replay {
+        run_replay(&GameRecord::load(path)?, Duration::from_millis(800))?;
+        return Ok(());
+    }
+
+    // 挑战码模式, 答案来自挑战码
+    if let Some(challenge) = opt.challenge.clone() {
+        challenge.apply(&mut opt, &final_set)?;
+    }
+    if opt.make_challenge {
+        let word = opt.word.clone().ok_or(WordError::CustomError(
+            t!("error.make_challenge").to_string(),
+        ))?;
+        let challenge = Challenge::new(&word, opt.difficult);
+        challenge.validate(&final_set)?;
+        println!("{}", challenge);
+        return Ok(());
+    }
+
+    // 助手模式和反向模式不需要答案
+    if opt.assist || opt.reverse {
+        let reverse = opt.reverse;
+        let wordle = Wordle {
+            opt,
+            mode: Mode::Interactive,
+            final_set,
+            acceptable_set,
+            ..Default::default()
+        };
+        if reverse {
+            wordle.run_reverse()?;
+        } else {
+            wordle.run_assistant()?;
+        }
+        return Ok(());
+    }
+
     // 随机答案模式
     let final_word: String = if opt.random {
-        let seed = if let Some(seed) = opt.seed {
-            seed
-        } else {
-            2048
-        };
+        let seed = opt.seed.unwrap_or(2048);
         let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
         let index: usize = rng.gen_range(0..ACCEPTABLE.len());
         ACCEPTABLE[index].to_string()
@@ -51,14 +98,31 @@ fn main() -> std::result::Result<(), Box<dyn Error>> {
             final_word
         }
     };
-    let wordle = Wordle {
+    let layout = KeyboardLayout::resolve(opt.layout.as_deref(), &acceptable_set)?;
+    // 提示策略和赛后分析需要逐对计算反馈时预先计算反馈矩阵
+    let patterns = if opt.strategy.uses_patterns() || opt.analyze {
+        Some(Arc::new(pattern_matrix(&acceptable_set, &final_set, None)?))
+    } else {
+        None
+    };
+    let mut wordle = Wordle {
         final_word: Word::parse(final_word)?,
+        layout,
+        patterns,
         opt,
         mode,
         final_set,
         acceptable_set,
         ..Default::default()
     };
-    wordle.run()?;
+    if wordle.opt.protocol == Protocol::Json {
+        wordle.run_protocol(std::io::stdin().lock(), std::io::stdout().lock())?;
+    } else {
+        if let Some(ref path) = wordle.opt.state {
+            wordle.statistics = WordleStatistic::load(path)?;
+        }
+        wordle.resume_autosave()?;
+        wordle.run()?;
+    }
     Ok(())
 }
@@ -0,0 +1,54 @@
+use std::error::Error;
+
+use structopt::StructOpt;
+use wordle::{
+    buildin_words::{ACCEPTABLE, FINAL},
+    command::TreeOpt,
+    solver::pattern_matrix,
+    tree::TreeBuilder,
+    wordle::{Wordle, MAX_RETRY_TIMES},
+};
+
+fn main() -> std::result::Result<(), Box<dyn Error>> {
+    let opt = TreeOpt::from_args();
+    let acceptable_set = match opt.acceptable_set {
+        Some(ref path) => Wordle::read_input_file(path)?,
+        None => ACCEPTABLE.iter().map(|s| s.to_string()).collect(),
+    };
+    let final_set = match opt.final_set {
+        Some(ref path) => Wordle::read_input_file(path)?,
+        None => FINAL.iter().map(|s| s.to_string()).collect(),
+    };
+    // 猜测词库包含所有答案
+    let matrix = pattern_matrix(&acceptable_set, &final_set, opt.cache.as_deref())?;
+    let tree = TreeBuilder::new(&matrix, opt.breadth)?.build(&opt.opener.to_lowercase())?;
+
+    if let Some(ref path) = opt.json {
+        std::fs::write(path, serde_json::to_string_pretty(&tree)?)?;
+    }
+    match opt.text {
+        Some(ref path) => std::fs::write(path, tree.to_text())?,
+        None => print!("{}", tree.to_text()),
+    }
+    eprintln!(
+        "{}: average {:.4}, worst case {} guesses, {}",
+        tree.guess,
+        tree.average(),
+        tree.depth,
+        if tree.within_limit() {
+            format!("solves every answer within {} guesses", MAX_RETRY_TIMES)
+        } else {
+            format!(
+                "cannot solve every answer within {} guesses",
+                MAX_RETRY_TIMES
+            )
+        }
+    );
+    // 每个节点只搜索部分候选词, 结果不一定最优
+    eprintln!(
+        "heuristic search over the top {} guesses at each node, the tree may not be optimal; \
+         raise --breadth to search more",
+        opt.breadth
+    );
+    Ok(())
+}
@@ -1,5 +1,6 @@
-use wordle::error::Result;
+use color_eyre::Result;
 
+/// 出错时终端已经恢复, 错误报告由`color_eyre`打印
 #[tokio::main]
 async fn main() -> Result<()> {
     #[cfg(feature = "tui")]
@@ -10,13 +11,24 @@ async fn main() -> Result<()> {
         use wordle::{
             buildin_words::{ACCEPTABLE, FINAL},
             command::Opt,
+            layout::KeyboardLayout,
+            record::GameRecord,
+            settings::Settings,
+            solver::pattern_matrix,
             state::Mode,
+            tui::{
+                keymap::Keymap,
+                theme::Theme,
+                ui::{MainState, UiState},
+            },
             word::Word,
-            wordle::Wordle,
+            wordle::{Wordle, WordleStatistic},
         };
         std::env::set_var("RUST_LOG", "info");
         pretty_env_logger::init();
-        let opt = Opt::from_args();
+        wordle::tui::controller::install_hooks()?;
+        let mut opt = Opt::from_args();
+        wordle::i18n::init(opt.locale);
 
         // get acceptable words
         let acceptable_set = match opt.acceptable_set {
@@ -29,13 +41,47 @@ async fn main() -> Result<()> {
             None => FINAL.iter().map(|s| s.to_string()).collect(),
         };
 
+        // 保存的设置, 命令行参数优先. 命令行参数只保存在opt中, 不会写入设置文件
+        opt.settings = opt.settings.take().or_else(Settings::default_path);
+        let settings = match opt.settings {
+            Some(ref path) => Settings::load(path).unwrap_or_default(),
+            None => Settings::default(),
+        };
+        opt.difficult |= settings.hard;
+        let layout = KeyboardLayout::resolve(opt.layout.as_deref(), &acceptable_set)?;
+        // 按键绑定, 有冲突时报告全部冲突并退出
+        let keymap = match opt.keymap {
+            Some(ref path) => Keymap::load(path)?,
+            None => match opt.settings {
+                Some(ref path) => Keymap::load(path.with_file_name("keymap.json"))?,
+                None => Keymap::default(),
+            },
+        };
+        let statistics = match opt.state {
+            Some(ref path) => WordleStatistic::load(path)?,
+            None => WordleStatistic::default(),
+        };
+
+        // 回放对局记录, 答案来自记录
+        let record = match opt.replay {
+            Some(ref path) => Some(GameRecord::load(path)?),
+            None => None,
+        };
+        if let Some(ref record) = record {
+            record.verify()?;
+            opt.word = Some(record.answer.clone());
+            opt.random = false;
+            opt.difficult = record.hard;
+        }
+
+        // 挑战码模式, 答案来自挑战码
+        if let Some(challenge) = opt.challenge.clone() {
+            challenge.apply(&mut opt, &final_set)?;
+        }
+
         // 随机答案模式
         let final_word = if opt.random {
-            let seed = if let Some(seed) = opt.seed {
-                seed
-            } else {
-                2048
-            };
+            let seed = opt.seed.unwrap_or(2048);
             let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
             let index: usize = rng.gen_range(0..final_set.len());
             final_set[index].to_string()
@@ -44,18 +90,52 @@ async fn main() -> Result<()> {
             opt.word.clone().unwrap_or(String::new())
         };
 
+        // 提示策略需要逐对计算反馈时预先计算反馈矩阵
+        let patterns = if opt.strategy.uses_patterns() {
+            Some(std::sync::Arc::new(pattern_matrix(
+                &acceptable_set,
+                &final_set,
+                None,
+            )?))
+        } else {
+            None
+        };
+        let theme = match opt.theme {
+            Some(ref theme) => Theme::resolve(Some(theme))?,
+            None => Theme::resolve(Some(&settings.theme)).unwrap_or_default(),
+        };
         let mut wordle = Wordle {
             opt,
+            theme,
+            settings,
+            statistics,
+            keymap,
+            layout,
+            patterns,
             mode: Mode::Tui,
             acceptable_set,
             final_set,
-            final_word: Word::parse(final_word).unwrap_or(Word::default()),
+            final_word: Word::parse(final_word).unwrap_or_default(),
             ..Default::default()
         };
+        if wordle.resume_autosave()? {
+            // 恢复未完成的一局
+            wordle.ui_state = UiState::Main(MainState::Main);
+        } else if let Some(record) = record {
+            wordle.ui_state = UiState::Main(MainState::Main);
+            wordle.replay_queue = record.guesses.into_iter().map(|g| g.word).collect();
+        } else if wordle.opt.assist {
+            wordle.ui_state = UiState::Assist;
+            wordle.states.suggestions = wordle.suggestions(5);
+        } else if wordle.opt.reverse {
+            wordle.ui_state = UiState::Reverse;
+            wordle::tui::action::next_reverse_guess(&mut wordle)?;
+        }
 
         wordle::tui::controller::run(&mut wordle).await?;
-        return Ok(());
+        Ok(())
     }
 
+    #[cfg(not(feature = "tui"))]
     panic!("请开启feature -> tui");
 }
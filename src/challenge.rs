@@ -0,0 +1,195 @@
+//! 挑战码
+//!
+//! 将答案, 单词长度, 猜测次数和困难模式编码为不直接暴露答案的挑战码,
+//! 朋友可以通过`--challenge <code>`玩同一个词. 编码只用于避免一眼看出答案, 并不是加密.
+//!
+//! 格式: 2字节校验和 + 以校验和为种子异或混淆的`[版本, 长度, 次数, 标志, 答案...]`,
+//! 再以Crockford Base32输出, 每4个字符以`-`分隔.
+use std::{fmt, str::FromStr};
+
+use crate::{
+    command::Opt,
+    error::{Result, WordError},
+    t,
+    word::Word,
+    wordle::MAX_RETRY_TIMES,
+};
+
+const VERSION: u8 = 1;
+const KEY: u32 = 0x5745_524c;
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+// 困难模式标志位
+const FLAG_DIFFICULT: u8 = 1;
+
+/// 一个挑战
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub word: String,
+    pub word_length: usize,
+    pub max_guesses: u32,
+    pub difficult: bool,
+}
+
+impl Challenge {
+    pub fn new(word: &str, difficult: bool) -> Self {
+        Challenge {
+            word: word.trim().to_lowercase(),
+            word_length: Word::MAX_LENGTH,
+            max_guesses: MAX_RETRY_TIMES,
+            difficult,
+        }
+    }
+
+    /// 生成挑战码
+    pub fn encode(&self) -> String {
+        let mut payload = vec![
+            VERSION,
+            self.word_length as u8,
+            self.max_guesses as u8,
+            if self.difficult { FLAG_DIFFICULT } else { 0 },
+        ];
+        payload.extend(self.word.bytes());
+        let checksum = checksum(&payload);
+        let mut bytes = checksum.to_be_bytes().to_vec();
+        bytes.extend(obfuscate(&payload, checksum));
+        base32_encode(&bytes)
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| String::from_utf8_lossy(chunk).to_string())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// 解析挑战码, 忽略大小写, 空白和`-`
+    pub fn decode(code: &str) -> Result<Self> {
+        let invalid = || WordError::CustomError(t!("error.challenge_code", code));
+        let bytes = base32_decode(code).ok_or_else(invalid)?;
+        if bytes.len() < 6 {
+            return Err(invalid());
+        }
+        let checksum = u16::from_be_bytes([bytes[0], bytes[1]]);
+        let payload = obfuscate(&bytes[2..], checksum);
+        if self::checksum(&payload) != checksum || payload[0] != VERSION {
+            return Err(invalid());
+        }
+        let word = String::from_utf8(payload[4..].to_vec()).map_err(|_| invalid())?;
+        if word.len() != payload[1] as usize || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Err(invalid());
+        }
+        Ok(Challenge {
+            word,
+            word_length: payload[1] as usize,
+            max_guesses: payload[2] as u32,
+            difficult: payload[3] & FLAG_DIFFICULT != 0,
+        })
+    }
+
+    /// 检查挑战在当前设置下是否可玩, 答案必须在`final_set`中
+    pub fn validate(&self, final_set: &[String]) -> Result<()> {
+        if self.word_length != Word::MAX_LENGTH {
+            return Err(WordError::CustomError(t!(
+                "error.unsupported_length",
+                self.word_length,
+                Word::MAX_LENGTH
+            )));
+        }
+        if self.max_guesses != MAX_RETRY_TIMES {
+            return Err(WordError::CustomError(t!(
+                "error.unsupported_guesses",
+                self.max_guesses,
+                MAX_RETRY_TIMES
+            )));
+        }
+        if !final_set
+            .iter()
+            .any(|word| word.eq_ignore_ascii_case(&self.word))
+        {
+            return Err(WordError::CustomError(
+                t!("error.challenge_word").to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 校验后将挑战应用到命令行参数: 指定答案, 并按需开启困难模式
+    pub fn apply(&self, opt: &mut Opt, final_set: &[String]) -> Result<()> {
+        self.validate(final_set)?;
+        opt.word = Some(self.word.clone());
+        opt.random = false;
+        opt.difficult |= self.difficult;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+impl FromStr for Challenge {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Challenge::decode(s)
+    }
+}
+
+/// FNV-1a哈希折叠为16位
+fn checksum(bytes: &[u8]) -> u16 {
+    let hash = bytes.iter().fold(0x811c_9dc5u32, |hash, &b| {
+        (hash ^ b as u32).wrapping_mul(0x0100_0193)
+    });
+    (hash >> 16) as u16 ^ hash as u16
+}
+
+/// 以xorshift生成的密钥流异或, 两次调用还原
+fn obfuscate(bytes: &[u8], seed: u16) -> Vec<u8> {
+    let mut state = KEY ^ (seed as u32) << 8 | 1;
+    bytes
+        .iter()
+        .map(|&b| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            b ^ state as u8
+        })
+        .collect()
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &b in bytes {
+        buffer = buffer << 8 | b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[(buffer >> bits & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[(buffer << (5 - bits) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(code: &str) -> Option<Vec<u8>> {
+    let mut out = vec![];
+    let (mut buffer, mut bits) = (0u32, 0);
+    for ch in code.chars().filter(|ch| *ch != '-' && !ch.is_whitespace()) {
+        let ch = match ch.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            ch => ch,
+        };
+        let value = ALPHABET.iter().position(|&a| a as char == ch)? as u32;
+        buffer = (buffer << 5 | value) & 0xfff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
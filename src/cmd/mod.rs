@@ -1,26 +1,36 @@
 use crate::{
+    assistant::parse_feedback_line,
     error::Result,
-    state::{Letter, LetterState, Mode},
+    pattern::Pattern,
+    record::GameRecord,
+    state::{no_color, Letter, LetterState, Mode},
+    t,
     word::Word,
     wordle::{CheckResult, Wordle, MAX_RETRY_TIMES},
 };
 use colored::Colorize;
-use std::io::{self, BufRead};
-
-/// 键盘上26个字母
-pub const KEYBOARD_KEYS: [char; 26] = [
-    'Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P', 'A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L',
-    'Z', 'X', 'C', 'V', 'B', 'N', 'M',
-];
+use std::{
+    io::{self, BufRead},
+    thread,
+    time::Duration,
+};
 
 impl Wordle {
     pub fn print(&self) {
         match self.mode {
             Mode::Test => {
                 // SSSSS AAAAAAAAAAAAAAAAAAAAAAAAAA
-                let guessed = self.states.current_word.to_string();
+                // 键盘状态按键盘布局中字母的顺序输出, 默认为qwerty
+                // 猜错后当前行已清空, 输出刚提交的猜测
+                let guessed = match self.history_words.last() {
+                    Some(word) if self.states.current_word.is_empty() => word.to_string(),
+                    _ => self.states.current_word.to_string(),
+                };
 
-                let keyboards = KEYBOARD_KEYS
+                let keyboards = self
+                    .layout
+                    .keys()
+                    .into_iter()
                     .map(|key| {
                         let cached_letter = self
                             .cached_letter_states
@@ -33,43 +43,17 @@ impl Wordle {
                         };
                         format!("{:?}", current_letter.1)
                     })
-                    .join("");
+                    .collect::<String>();
                 println!("{} {}", guessed, keyboards);
             }
             Mode::Interactive => {
                 // 输出历史单词
                 for word in self.history_words.iter() {
-                    for letter in word.get_letters().iter() {
-                        let formated_string = match letter.1 {
-                            crate::state::LetterState::G => letter.0.to_string().green(),
-                            crate::state::LetterState::Y => letter.0.to_string().yellow(),
-                            crate::state::LetterState::R => letter.0.to_string().red(),
-                            crate::state::LetterState::X => letter.0.to_string().black(),
-                        };
-                        print!("{}", formated_string);
-                    }
-                    println!();
+                    println!("{}", colored_word(word));
                 }
-                if self.opt.difficult && !self.difficult_error_letters.is_empty() {
-                    let mut has_green = false;
-                    self.difficult_error_letters
-                        .iter()
-                        .filter(|green| green.1 .1 == LetterState::G)
-                        .for_each(|(index, letter)| {
-                            println!("{}th letter must be {}", *index + 1, letter.0);
-                            has_green = true;
-                        });
-                    if !has_green {
-                        let letters = self
-                            .difficult_error_letters
-                            .iter()
-                            .filter(|green| green.1 .1 == LetterState::Y)
-                            .map(|(_, letter)| letter.0.to_string())
-                            .collect::<Vec<_>>()
-                            .join(",");
-                        if !letters.is_empty() {
-                            println!("the word must contain {}", letters);
-                        }
+                if self.opt.difficult {
+                    for message in self.difficult_error_messages() {
+                        println!("{}", message);
                     }
                 }
             }
@@ -83,53 +67,72 @@ impl Wordle {
     pub fn run(mut self) -> Result<()> {
         // check final word
         let mut stdin = io::stdin().lock();
-        // 随机模式中，添加--day/-d 用于指定开始时的局数，-d 5表示从第5局开始， 跳过前4局
-        if self.opt.random && self.opt.day.is_some() {
-            self.states.current_try_times = self.opt.day.unwrap();
+        // 恢复自动保存的一局时不重新开始
+        if !self.journal.in_progress() {
+            self.start_game()?;
+            // 随机模式中，添加--day/-d 用于指定开始时的局数，-d 5表示从第5局开始， 跳过前4局
+            if let (true, Some(day)) = (self.opt.random, self.opt.day) {
+                self.states.current_try_times = day;
+            }
+        } else {
+            self.print();
         }
 
         loop {
-            self.states.current_try_times += 1;
-            if self.states.current_try_times > MAX_RETRY_TIMES {
-                eprintln!("{} {}", "FAILED".red(), self.final_word.to_string().green());
+            let attempt = self.states.current_try_times + 1;
+            if attempt > MAX_RETRY_TIMES {
+                self.finish_game(false)?;
                 break;
             }
-            println!(
-                "{} {} {}",
-                "Please enter the 5-letter(only 26 letters) word for your".green(),
-                self.states.current_try_times,
-                "attempt!".green()
-            );
+            let prompt = if self.opt.practice {
+                t!("cmd.prompt_practice", attempt)
+            } else {
+                t!("cmd.prompt", attempt)
+            };
+            println!("{}", prompt.green());
             let mut word = String::new();
-            stdin.read_line(&mut word)?;
+            if stdin.read_line(&mut word)? == 0 {
+                // 输入结束, 未完成的一局可以通过`--autosave`恢复
+                return Ok(());
+            }
+            if word.trim() == "?" {
+                // 提示不消耗次数
+                match self.hint() {
+                    Some(hint) => println!("{} {}", t!("cmd.hint").blue(), hint),
+                    None => eprintln!("{}", t!("cmd.no_fit")),
+                }
+                continue;
+            }
+            if self.opt.practice && word.trim() == "undo" {
+                match self.undo() {
+                    Ok(()) if self.mode == Mode::Interactive => self.print(),
+                    Ok(()) => {}
+                    Err(e) => eprintln!("{}", e.to_string().red()),
+                }
+                continue;
+            }
             if let Ok(w) = Word::parse(word.trim()) {
-                self.states.current_word = w;
+                self.type_word(&w.to_string())?;
                 // word 在final set 中并且在acceptable set中， 判断word是否正确， 以及各个位置的字母是否符合要求
-                let check_result = self.check_word();
-
-                {
-                    match check_result {
-                        CheckResult::InValid => {
-                            // 不消耗次数
-                            self.states.current_try_times -= 1;
-                            eprintln!("INVALID");
-                            continue;
-                        }
-                        CheckResult::Success => {
-                            self.print();
-                            println!("{} {}", "CORRECT".green(), self.states.current_try_times);
+                match self.submit_guess()? {
+                    CheckResult::InValid => {
+                        // 不消耗次数
+                        eprintln!("INVALID");
+                    }
+                    CheckResult::Success => {
+                        self.print();
+                        self.finish_game(true)?;
+                        break;
+                    }
+                    CheckResult::Wrong => {
+                        self.print();
+                        if self.is_game_over() {
+                            self.finish_game(false)?;
                             break;
                         }
-                        CheckResult::Wrong => {
-                            self.print();
-                        }
-                        CheckResult::Difficult => {
-                            if self.opt.difficult {
-                                self.print();
-                                self.states.current_try_times -= 1;
-                                continue;
-                            }
-                        }
+                    }
+                    CheckResult::Difficult => {
+                        self.print();
                     }
                 }
             } else {
@@ -140,7 +143,7 @@ impl Wordle {
             // 非指定单词模式下， 询问是否开始下一句
             let mut w = String::new();
             {
-                println!("new game? type `y` to continue, enter any other letters to finish!");
+                println!("{}", t!("cmd.new_game"));
                 stdin.read_line(&mut w)?;
                 // release stdin lock.
                 drop(stdin);
@@ -153,4 +156,245 @@ impl Wordle {
 
         Ok(())
     }
+
+    ///
+    /// 输出本局结果, 对局记录, 分享文本和分析
+    ///
+    fn finish_game(&self, won: bool) -> Result<()> {
+        if won {
+            println!(
+                "{} {}",
+                "CORRECT".green(),
+                self.states.current_try_times + 1
+            );
+        } else {
+            eprintln!("{} {}", "FAILED".red(), self.final_word.to_string().green());
+        }
+        self.print_record()?;
+        self.print_share();
+        if self.opt.analyze {
+            self.print_analysis();
+        }
+        Ok(())
+    }
+
+    ///
+    /// 指定了`--record`时写入对局记录
+    ///
+    pub fn print_record(&self) -> Result<()> {
+        if let Some(path) = self.save_record()? {
+            eprintln!("{}", t!("cmd.record_saved", path.display()));
+        }
+        Ok(())
+    }
+
+    ///
+    /// 交互模式下输出分享文本, 测试模式的输出格式保持不变
+    ///
+    pub fn print_share(&self) {
+        if let Mode::Interactive = self.mode {
+            println!("\n{}\n", self.share_text());
+        }
+    }
+
+    ///
+    /// 输出本局每次猜测的分析
+    ///
+    pub fn print_analysis(&self) {
+        let analysis = self.analyze();
+        println!(
+            "{:<3} {:<6} {:<6} {:>6} {:>6} {:>6} {:>6} {:<6} {:>6} {:>6} {}",
+            "#",
+            t!("review.guess"),
+            t!("review.colors"),
+            t!("review.before"),
+            t!("review.after"),
+            t!("review.info"),
+            t!("review.got"),
+            t!("review.best"),
+            t!("review.info"),
+            t!("review.skill"),
+            t!("review.luck")
+        );
+        for (index, guess) in analysis.guesses.iter().enumerate() {
+            let luck = format!("{:+.2}", guess.luck);
+            println!(
+                "{:<3} {:<6} {:<6} {:>6} {:>6} {:>6.2} {:>6.2} {:<6} {:>6.2} {:>6} {}",
+                index + 1,
+                guess.guess,
+                guess.pattern,
+                guess.candidates_before,
+                guess.candidates_after,
+                guess.expected_info,
+                guess.actual_info,
+                guess.best_guess,
+                guess.best_info,
+                guess.skill,
+                if guess.luck >= 0.0 {
+                    luck.green()
+                } else {
+                    luck.red()
+                }
+            );
+        }
+        println!(
+            "{} {}/100, {} {}",
+            t!("review.skill").blue(),
+            analysis.skill,
+            t!("review.luck").blue(),
+            t!("review.bits", format!("{:+.2}", analysis.luck))
+        );
+    }
+
+    ///
+    /// 助手模式: 读取外部游戏的猜测和反馈, 输出剩余候选词和推荐
+    ///
+    pub fn run_assistant(mut self) -> Result<()> {
+        let stdin = io::stdin().lock();
+        println!("{}", t!("cmd.assist.help").green());
+        for line in stdin.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                break;
+            }
+            let recorded = parse_feedback_line(&line)
+                .and_then(|(guess, pattern)| self.record_feedback(&guess, pattern));
+            if let Err(e) = recorded {
+                eprintln!("{}", e.to_string().red());
+                continue;
+            }
+            self.print();
+            if self.is_game_over() {
+                println!(
+                    "{} {}",
+                    t!("cmd.correct").green(),
+                    self.states.current_try_times + 1
+                );
+                break;
+            }
+            let candidates = self.remaining_candidates();
+            if candidates.is_empty() {
+                eprintln!("{}", t!("cmd.no_fit_final").red());
+                continue;
+            }
+            println!("{}", t!("cmd.remaining", candidates.len()));
+            if candidates.len() <= 20 {
+                println!("{}", candidates.join(" "));
+            }
+            println!(
+                "{} {}",
+                t!("cmd.suggestions").blue(),
+                self.suggestions(5).join(" ")
+            );
+        }
+        Ok(())
+    }
+
+    ///
+    /// 反向模式: 电脑猜测玩家想好的词, 玩家输入每次猜测的颜色反馈
+    ///
+    pub fn run_reverse(mut self) -> Result<()> {
+        let mut stdin = io::stdin().lock();
+        println!("{}", t!("cmd.reverse.help").green());
+        while !self.is_game_over() {
+            let Some(guess) = self.reverse_guess() else {
+                eprintln!("{}", t!("cmd.reverse.no_fit").red());
+                return Ok(());
+            };
+            loop {
+                println!(
+                    "{}",
+                    t!("cmd.reverse.guess", self.history_words.len() + 1, guess).blue()
+                );
+                let mut line = String::new();
+                if stdin.read_line(&mut line)? == 0 {
+                    return Ok(());
+                }
+                let result = line
+                    .parse::<Pattern>()
+                    .and_then(|pattern| self.reverse_feedback(&guess, pattern));
+                match result {
+                    Ok(remaining) => {
+                        self.print();
+                        if !self.is_game_over() {
+                            println!("{}", t!("cmd.remaining", remaining));
+                        }
+                        break;
+                    }
+                    Err(e) => eprintln!("{}", e.to_string().red()),
+                }
+            }
+        }
+        if self.reverse_solved() {
+            println!(
+                "{}",
+                t!("cmd.reverse.solved", self.history_words.len()).green()
+            );
+        } else {
+            println!("{}", t!("cmd.reverse.failed", MAX_RETRY_TIMES).red());
+        }
+        Ok(())
+    }
+}
+
+/// 按字母状态着色的单词, 设置了`NO_COLOR`时用`[a]`表示绿色, `(a)`表示黄色
+pub fn colored_word(word: &Word) -> String {
+    if no_color() {
+        return word
+            .get_letters()
+            .iter()
+            .map(|letter| match letter.1 {
+                LetterState::G => format!("[{}]", letter.0),
+                LetterState::Y => format!("({})", letter.0),
+                LetterState::R | LetterState::X => format!(" {} ", letter.0),
+            })
+            .collect();
+    }
+    word.get_letters()
+        .iter()
+        .map(|letter| {
+            let letter_string = letter.0.to_string();
+            match letter.1 {
+                LetterState::G => letter_string.green(),
+                LetterState::Y => letter_string.yellow(),
+                LetterState::R => letter_string.red(),
+                LetterState::X => letter_string.black(),
+            }
+            .to_string()
+        })
+        .collect()
+}
+
+///
+/// 校验对局记录并逐个输出猜测, 每次猜测之间等待`delay`
+///
+pub fn run_replay(record: &GameRecord, delay: Duration) -> Result<()> {
+    record.verify()?;
+    println!(
+        "{} {} {}",
+        t!("cmd.replay.answer").blue(),
+        record.answer,
+        if record.hard {
+            t!("cmd.replay.hard")
+        } else {
+            ""
+        }
+    );
+    let started = record.guesses.first().map_or(0, |g| g.timestamp);
+    for (index, guess) in record.guesses.iter().enumerate() {
+        thread::sleep(delay);
+        let word = Word::with_states(&guess.word, &guess.pattern.states())?;
+        println!(
+            "{}. {} (+{:.1}s)",
+            index + 1,
+            colored_word(&word),
+            guess.timestamp.saturating_sub(started) as f64 / 1000.0
+        );
+    }
+    if record.solved() {
+        println!("{} {}", t!("cmd.correct").green(), record.result());
+    } else {
+        println!("{} {}", "FAILED".red(), record.result());
+    }
+    Ok(())
 }
@@ -2,7 +2,13 @@ use std::path::PathBuf;
 
 use structopt::StructOpt;
 
+use crate::bench::BenchMode;
+use crate::challenge::Challenge;
 use crate::error::{Result, WordError};
+use crate::i18n::Locale;
+use crate::protocol::Protocol;
+use crate::solver::StrategyKind;
+use crate::t;
 
 #[derive(StructOpt, Debug, Default)]
 #[structopt(author = "yaphets", about = "wordle game in terminal usage.")]
@@ -33,7 +39,7 @@ pub struct Opt {
     #[structopt(
         short = "a",
         long = "acceptable-set",
-        help = "acceptable set from an input file" 
+        help = "acceptable set from an input file"
     )]
     pub acceptable_set: Option<PathBuf>,
 
@@ -43,7 +49,7 @@ pub struct Opt {
     #[structopt(
         short = "S",
         long,
-        help = "save or load game state from path `state.json`"
+        help = "save or load game statistics from path `state.json`"
     )]
     pub state: Option<PathBuf>,
 
@@ -56,12 +62,251 @@ pub struct Opt {
     pub day: Option<u32>,
     #[structopt(short = "s", long, help = "seed for rand")]
     pub seed: Option<u64>,
+
+    #[structopt(
+        long,
+        default_value = "text",
+        help = "input/output protocol, `text` or `json` (JSON lines for bots)"
+    )]
+    pub protocol: Protocol,
+
+    #[structopt(
+        long,
+        default_value = "frequency",
+        help = "solver strategy for hints: random, frequency, entropy or minimax"
+    )]
+    pub strategy: StrategyKind,
+
+    #[structopt(
+        long,
+        help = "assistant model: enter guesses and colors from another game, e.g. `crane GYXXR`"
+    )]
+    pub assist: bool,
+
+    #[structopt(
+        long,
+        help = "reverse model: think of a word and let the computer guess it"
+    )]
+    pub reverse: bool,
+
+    #[structopt(
+        long,
+        help = "print a per-guess review of skill and luck after each game"
+    )]
+    pub analyze: bool,
+
+    #[structopt(
+        long,
+        help = "use orange and blue instead of green and yellow in the share text"
+    )]
+    pub high_contrast: bool,
+
+    #[structopt(
+        long,
+        help = "play the puzzle encoded in a challenge code from a friend"
+    )]
+    pub challenge: Option<Challenge>,
+
+    #[structopt(long, help = "print a challenge code for `--word` and exit")]
+    pub make_challenge: bool,
+
+    #[structopt(long, help = "write a game record into this directory after each game")]
+    pub record: Option<PathBuf>,
+
+    #[structopt(long, help = "replay a game record and verify its feedback")]
+    pub replay: Option<PathBuf>,
+
+    #[structopt(long, help = "practice model: allow undoing guesses")]
+    pub practice: bool,
+
+    #[structopt(
+        long,
+        help = "append every game event to this file and resume an unfinished game from it"
+    )]
+    pub autosave: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        help = "tui color theme: dark, light, high-contrast, color-blind, mono or a json theme file"
+    )]
+    pub theme: Option<String>,
+
+    #[structopt(
+        long,
+        help = "message language: en or zh, default from WORDLE_LOCALE or LANG"
+    )]
+    pub locale: Option<Locale>,
+
+    #[structopt(
+        long,
+        help = "settings file for the tui, default `~/.config/wordle/settings.json`"
+    )]
+    pub settings: Option<PathBuf>,
+
+    #[structopt(long, help = "disable tui tile animations")]
+    pub no_animation: bool,
+
+    #[structopt(
+        long,
+        help = "tui key bindings file, default `keymap.json` next to the settings file"
+    )]
+    pub keymap: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        help = "keyboard layout: qwerty, azerty, qwertz, dvorak, colemak, alphabetical or auto (from the word list)"
+    )]
+    pub layout: Option<String>,
 }
 
 fn parse_day(src: &str) -> Result<u32> {
     let target = src.parse::<u32>()?;
     if !(1..=6).contains(&target) {
-        return Err(WordError::CustomError("day must be in 1..=6".to_string()));
+        return Err(WordError::CustomError(t!("error.day").to_string()));
     }
     Ok(target)
 }
+
+#[derive(StructOpt, Debug)]
+#[structopt(about = "run external wordle bots against the same answers and rank them.")]
+pub struct ArenaOpt {
+    #[structopt(
+        short,
+        long = "bot",
+        required = true,
+        help = "bot command line, may be given multiple times"
+    )]
+    pub bots: Vec<String>,
+
+    #[structopt(short, long, default_value = "100", help = "number of games per bot")]
+    pub games: usize,
+
+    #[structopt(
+        short,
+        long,
+        default_value = "2048",
+        help = "seed for the answer sequence"
+    )]
+    pub seed: u64,
+
+    #[structopt(
+        short = "D",
+        long,
+        help = "whether or not startup difficult model, default `false`"
+    )]
+    pub difficult: bool,
+
+    #[structopt(
+        short,
+        long,
+        default_value = "5000",
+        help = "timeout in milliseconds for each guess"
+    )]
+    pub timeout: u64,
+
+    #[structopt(
+        long,
+        default_value = "10",
+        help = "invalid commands allowed per game before forfeit"
+    )]
+    pub max_errors: u32,
+
+    #[structopt(short = "f", long = "final-set", help = "final set from an input file")]
+    pub final_set: Option<PathBuf>,
+
+    #[structopt(
+        short = "a",
+        long = "acceptable-set",
+        help = "acceptable set from an input file"
+    )]
+    pub acceptable_set: Option<PathBuf>,
+
+    #[structopt(long, help = "print the leaderboard as json")]
+    pub json: bool,
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(about = "benchmark a solver strategy against the final word list.")]
+pub struct BenchOpt {
+    #[structopt(
+        long,
+        default_value = "frequency",
+        help = "solver strategy: random, frequency, entropy or minimax"
+    )]
+    pub strategy: StrategyKind,
+
+    #[structopt(
+        short,
+        long,
+        default_value = "both",
+        help = "difficulty to run: normal, hard or both"
+    )]
+    pub mode: BenchMode,
+
+    #[structopt(long, help = "only play a seeded sample of this many answers")]
+    pub sample: Option<usize>,
+
+    #[structopt(
+        short,
+        long,
+        default_value = "2048",
+        help = "seed for sampling and strategies"
+    )]
+    pub seed: u64,
+
+    #[structopt(long, default_value = "10", help = "number of slowest words to report")]
+    pub slowest: usize,
+
+    #[structopt(short = "f", long = "final-set", help = "final set from an input file")]
+    pub final_set: Option<PathBuf>,
+
+    #[structopt(
+        short = "a",
+        long = "acceptable-set",
+        help = "acceptable set from an input file"
+    )]
+    pub acceptable_set: Option<PathBuf>,
+
+    #[structopt(long, help = "print the reports as json")]
+    pub json: bool,
+
+    #[structopt(
+        long,
+        help = "directory for the cached pattern matrix used by entropy and minimax"
+    )]
+    pub cache: Option<PathBuf>,
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(about = "compute and export a decision tree for an opening word.")]
+pub struct TreeOpt {
+    #[structopt(short, long, default_value = "salet", help = "opening word")]
+    pub opener: String,
+
+    #[structopt(
+        short,
+        long,
+        default_value = "5",
+        help = "number of highest-entropy guesses searched at each node; the search is heuristic, larger values are slower but closer to optimal"
+    )]
+    pub breadth: usize,
+
+    #[structopt(long, help = "directory for the cached pattern matrix")]
+    pub cache: Option<PathBuf>,
+
+    #[structopt(long, help = "write the tree as json to this path")]
+    pub json: Option<PathBuf>,
+
+    #[structopt(long, help = "write the text tree to this path instead of stdout")]
+    pub text: Option<PathBuf>,
+
+    #[structopt(short = "f", long = "final-set", help = "final set from an input file")]
+    pub final_set: Option<PathBuf>,
+
+    #[structopt(
+        short = "a",
+        long = "acceptable-set",
+        help = "acceptable set from an input file"
+    )]
+    pub acceptable_set: Option<PathBuf>,
+}
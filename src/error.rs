@@ -2,22 +2,24 @@ use std::{io, num::ParseIntError};
 
 use thiserror::Error as ThisError;
 
+use crate::t;
+
 pub type Result<T> = std::result::Result<T, WordError>;
 
 #[derive(Debug, ThisError)]
 pub enum WordError {
-    #[error("io error")]
+    #[error("{}", t!("error.io"))]
     IoError(#[from] io::Error),
-    #[error("json error")]
-    JsonError,
-    #[error("your word `{0}` is not in the acceptable word list")]
+    #[error("{}", t!("error.json", .0))]
+    JsonError(#[from] serde_json::Error),
+    #[error("{}", t!("error.invalid_word", .0))]
     InValidWord(String),
-    #[error("parse int error")]
+    #[error("{}", t!("error.parse_int"))]
     ParseIntError(#[from] ParseIntError),
-    #[error("custom error for: {0}")]
+    #[error("{}", t!("error.custom", .0))]
     CustomError(String),
-    #[error("eyre error")]
+    #[error("{}", t!("error.eyre"))]
     EyreError(#[from] color_eyre::eyre::Error),
-    #[error("unknown error")]
+    #[error("{}", t!("error.unknown"))]
     UnknownError,
 }
@@ -0,0 +1,150 @@
+//! 练习模式的分支探索
+//!
+//! 练习模式下可以回到任意一行重新猜测. 每次提交和撤销都记录在事件日志中,
+//! 因此可以从日志推导出所有尝试过的分支: 提交猜测时进入子节点, 撤销时回到父节点.
+use crate::{
+    error::{Result, WordError},
+    journal::GameEvent,
+    pattern::Pattern,
+    solver::Context,
+    t,
+    word::Word,
+    wordle::Wordle,
+};
+
+/// 分支树中的一个节点, 以先序遍历展开
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchNode {
+    // 从第一次猜测到该节点的猜测序列
+    pub path: Vec<String>,
+    // 该节点的猜测及反馈
+    pub guess: Word,
+    // 该节点之后剩余的候选答案数
+    pub candidates: usize,
+    // 是否在当前分支上
+    pub active: bool,
+}
+
+impl BranchNode {
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+}
+
+struct Node {
+    parent: Option<usize>,
+    guess: Word,
+    children: Vec<usize>,
+}
+
+impl Wordle {
+    ///
+    /// 根据事件日志展开本局尝试过的所有分支
+    ///
+    pub fn branch_tree(&self) -> Vec<BranchNode> {
+        let mut nodes: Vec<Node> = vec![];
+        let mut roots: Vec<usize> = vec![];
+        let mut cursor: Option<usize> = None;
+        for event in self.journal.events.iter() {
+            match event {
+                GameEvent::Started { .. } => {
+                    nodes.clear();
+                    roots.clear();
+                    cursor = None;
+                }
+                GameEvent::GuessSubmitted { word, pattern, .. } => {
+                    let Ok(pattern) = pattern.parse::<Pattern>() else {
+                        continue;
+                    };
+                    let Ok(guess) = Word::with_states(word, &pattern.states()) else {
+                        continue;
+                    };
+                    let siblings = match cursor {
+                        Some(index) => &nodes[index].children,
+                        None => &roots,
+                    };
+                    let existing = siblings
+                        .iter()
+                        .copied()
+                        .find(|&child| nodes[child].guess.to_string() == guess.to_string());
+                    cursor = Some(existing.unwrap_or_else(|| {
+                        nodes.push(Node {
+                            parent: cursor,
+                            guess,
+                            children: vec![],
+                        });
+                        let index = nodes.len() - 1;
+                        match cursor {
+                            Some(parent) => nodes[parent].children.push(index),
+                            None => roots.push(index),
+                        }
+                        index
+                    }));
+                }
+                GameEvent::Undone { .. } => {
+                    cursor = cursor.and_then(|index| nodes[index].parent);
+                }
+                _ => {}
+            }
+        }
+
+        let mut active = vec![];
+        let mut current = cursor;
+        while let Some(index) = current {
+            active.push(index);
+            current = nodes[index].parent;
+        }
+
+        let mut out = vec![];
+        let mut stack = roots.iter().rev().map(|&r| (r, vec![])).collect::<Vec<_>>();
+        while let Some((index, mut history)) = stack.pop() {
+            let node = &nodes[index];
+            history.push(node.guess.clone());
+            let context = Context {
+                history: &history,
+                ..Context::new(self)
+            };
+            out.push(BranchNode {
+                path: history.iter().map(|w| w.to_string()).collect(),
+                guess: node.guess.clone(),
+                candidates: context.candidates().len(),
+                active: active.contains(&index),
+            });
+            for &child in node.children.iter().rev() {
+                stack.push((child, history.clone()));
+            }
+        }
+        out
+    }
+
+    ///
+    /// 练习模式下回到第`row`行之前, 即只保留前`row`次猜测
+    ///
+    pub fn rewind(&mut self, row: usize) -> Result<()> {
+        while self.history_words.len() > row {
+            self.undo()?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// 练习模式下切换到`path`所在的分支: 回到公共前缀后依次提交其余的猜测
+    ///
+    pub fn switch_branch(&mut self, path: &[String]) -> Result<()> {
+        let common = self
+            .history_words
+            .iter()
+            .zip(path.iter())
+            .take_while(|(word, guess)| word.to_string().eq_ignore_ascii_case(guess))
+            .count();
+        self.rewind(common)?;
+        for guess in path[common..].iter() {
+            self.type_word(guess)?;
+            self.submit_guess()?;
+            if self.history_words.last().map(|w| w.to_string()) != Some(guess.to_string()) {
+                return Err(WordError::CustomError(t!("error.replay_branch", guess)));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,607 @@
+//! 多语言消息目录
+//!
+//! 界面文字按键名在目录中查找, 目前支持英文和简体中文. 语言由`--locale`指定,
+//! 未指定时依次读取`WORDLE_LOCALE`, `LC_ALL`, `LC_MESSAGES`, `LANG`环境变量, 默认英文.
+//! 测试模式的`CORRECT`/`FAILED`/`INVALID`等输出供程序解析, 不做翻译.
+use std::{
+    env,
+    fmt::Display,
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crate::error::WordError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En = 0,
+    // 简体中文
+    Zh = 1,
+}
+
+impl FromStr for Locale {
+    type Err = WordError;
+
+    /// 接受`en`, `zh`, `zh-CN`, `zh_CN.UTF-8`等写法
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match language.as_str() {
+            "en" => Ok(Locale::En),
+            "zh" => Ok(Locale::Zh),
+            _ => Err(WordError::CustomError(fill(tr("error.locale"), &[&s]))),
+        }
+    }
+}
+
+impl Locale {
+    ///
+    /// 从环境变量中读取语言, `C`/`POSIX`和不支持的语言会被跳过
+    ///
+    pub fn from_env() -> Option<Locale> {
+        ["WORDLE_LOCALE", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find_map(|value| value.parse().ok())
+    }
+}
+
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+/// 当前使用的语言
+pub fn locale() -> Locale {
+    match LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::Zh,
+        _ => Locale::En,
+    }
+}
+
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+///
+/// 程序启动时设置语言: 优先使用命令行参数, 其次是环境变量
+///
+pub fn init(locale: Option<Locale>) {
+    set_locale(locale.or_else(Locale::from_env).unwrap_or_default());
+}
+
+///
+/// 查找当前语言的消息, 目录中没有该键时返回键名本身
+///
+pub fn tr(key: &'static str) -> &'static str {
+    CATALOG
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map_or(key, |(_, en, zh)| match locale() {
+            Locale::En => en,
+            Locale::Zh => zh,
+        })
+}
+
+///
+/// 依次用`args`替换模板中的`{}`
+///
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(index) = rest.find("{}") {
+        out.push_str(&rest[..index]);
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        rest = &rest[index + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 翻译消息, 带参数时返回替换后的`String`
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill(
+            $crate::i18n::tr($key),
+            &[$(&$arg as &dyn std::fmt::Display),+],
+        )
+    };
+}
+
+/// 消息目录: (键, 英文, 简体中文)
+const CATALOG: &[(&str, &str, &str)] = &[
+    // 错误信息
+    ("error.io", "io error", "读写错误"),
+    ("error.json", "json error: {}", "json 错误: {}"),
+    (
+        "error.invalid_word",
+        "your word `{}` is not in the acceptable word list",
+        "单词 `{}` 不在可接受的词库中",
+    ),
+    ("error.parse_int", "parse int error", "整数解析错误"),
+    ("error.custom", "custom error for: {}", "错误: {}"),
+    ("error.eyre", "eyre error", "eyre 错误"),
+    ("error.unknown", "unknown error", "未知错误"),
+    (
+        "error.locale",
+        "unknown locale `{}`, expect en or zh",
+        "不支持的语言 `{}`, 可选 en 或 zh",
+    ),
+    (
+        "error.undo_practice",
+        "undo is only available in practice mode",
+        "只有练习模式可以撤销",
+    ),
+    ("error.nothing_to_undo", "nothing to undo", "没有可以撤销的猜测"),
+    (
+        "error.replay_branch",
+        "cannot replay guess `{}` on this branch",
+        "无法在该分支上重新提交猜测 `{}`",
+    ),
+    (
+        "error.reverse_inconsistent",
+        "feedback {} contradicts the earlier feedback, please check the colors",
+        "反馈 {} 与之前的反馈矛盾, 请检查颜色",
+    ),
+    (
+        "error.feedback_format",
+        "expect `<guess> <feedback>`, e.g. `crane GYXXR`",
+        "格式应为 `<猜测词> <反馈>`, 例如 `crane GYXXR`",
+    ),
+    ("error.no_guesses_left", "no guesses left", "猜测次数已用完"),
+    (
+        "error.unsupported_length",
+        "{}-letter words are not supported, only {} letters are",
+        "不支持 {} 个字母的单词, 只支持 {} 个字母",
+    ),
+    (
+        "error.unsupported_guesses",
+        "{} guesses are not supported, only {} are",
+        "不支持 {} 次猜测, 只支持 {} 次",
+    ),
+    (
+        "error.word_file",
+        "input file is empty or invalid",
+        "输入的词库文件为空或无效",
+    ),
+    (
+        "error.feedback_letter",
+        "invalid feedback letter `{}`, expect G, Y or X",
+        "无效的反馈字母 `{}`, 应为 G, Y 或 X",
+    ),
+    (
+        "error.feedback_length",
+        "feedback `{}` must have {} letters",
+        "反馈 `{}` 必须有 {} 个字母",
+    ),
+    ("error.day", "day must be in 1..=6", "天数必须在 1..=6 之间"),
+    (
+        "error.protocol",
+        "unknown protocol `{}`, expect `text` or `json`",
+        "未知的协议 `{}`, 可选 `text` 或 `json`",
+    ),
+    (
+        "error.bench_mode",
+        "unknown mode `{}`, expect `normal`, `hard` or `both`",
+        "未知的模式 `{}`, 可选 `normal`, `hard` 或 `both`",
+    ),
+    (
+        "error.strategy",
+        "unknown strategy `{}`, expect one of {}",
+        "未知的策略 `{}`, 可选 {}",
+    ),
+    ("error.bot_command", "empty bot command", "机器人命令为空"),
+    ("error.theme_file", "invalid theme file: {}", "无效的主题文件: {}"),
+    (
+        "error.make_challenge",
+        "`--make-challenge` requires `--word`",
+        "`--make-challenge` 需要同时指定 `--word`",
+    ),
+    (
+        "error.challenge_code",
+        "invalid challenge code `{}`",
+        "无效的挑战码 `{}`",
+    ),
+    (
+        "error.challenge_word",
+        "the challenge word is not in the final set",
+        "挑战的答案不在答案词库中",
+    ),
+    (
+        "error.record_line",
+        "invalid game record line `{}`",
+        "无效的对局记录行 `{}`",
+    ),
+    (
+        "error.record_answer",
+        "game record has no `Answer` tag",
+        "对局记录缺少 `Answer` 标签",
+    ),
+    (
+        "error.record_too_long",
+        "{} guesses recorded, at most {} allowed",
+        "记录了 {} 次猜测, 最多允许 {} 次",
+    ),
+    (
+        "error.record_mismatch",
+        "guess {} `{}`: recorded {} but the answer gives {}",
+        "第 {} 次猜测 `{}`: 记录的反馈为 {}, 但答案给出的是 {}",
+    ),
+    (
+        "error.record_extra",
+        "guess {} `{}` solved the game but more guesses follow",
+        "第 {} 次猜测 `{}` 已经猜中, 但之后还有猜测",
+    ),
+    // 困难模式的提示
+    (
+        "hard.position",
+        "{}th letter must be {}",
+        "第 {} 个字母必须是 {}",
+    ),
+    (
+        "hard.contain",
+        "the word must contain {}",
+        "必须包含字母 {}",
+    ),
+    // 复盘, tui和cmd共用
+    ("review.guess", "guess", "猜测"),
+    ("review.colors", "colors", "反馈"),
+    ("review.before", "before", "猜前"),
+    ("review.after", "after", "猜后"),
+    ("review.info", "info", "期望"),
+    ("review.got", "got", "实际"),
+    ("review.best", "best", "最佳"),
+    ("review.skill", "skill", "技巧"),
+    ("review.luck", "luck", "运气"),
+    ("review.bits", "{} bits", "{} 比特"),
+    // cmd
+    (
+        "cmd.prompt",
+        "Please enter the 5-letter(only 26 letters) word for your {} attempt! (type `?` for a hint)",
+        "请输入第 {} 次猜测的5个字母的单词(只能是26个字母)! (输入 `?` 获取提示)",
+    ),
+    (
+        "cmd.prompt_practice",
+        "Please enter the 5-letter(only 26 letters) word for your {} attempt! (type `?` for a hint, `undo` to take back a guess)",
+        "请输入第 {} 次猜测的5个字母的单词(只能是26个字母)! (输入 `?` 获取提示, 输入 `undo` 撤销上一次猜测)",
+    ),
+    ("cmd.hint", "HINT", "提示"),
+    (
+        "cmd.final_word.prompt",
+        "please enter the specified final word: ",
+        "请输入指定的答案: ",
+    ),
+    ("cmd.final_word.invalid", "INVALID final word.", "答案无效."),
+    ("cmd.no_fit", "no word fits the feedback", "没有符合反馈的词"),
+    (
+        "cmd.new_game",
+        "new game? type `y` to continue, enter any other letters to finish!",
+        "开始新的一局? 输入 `y` 继续, 输入其他字母结束!",
+    ),
+    (
+        "cmd.record_saved",
+        "game record saved to {}",
+        "对局记录已保存到 {}",
+    ),
+    (
+        "cmd.assist.help",
+        "enter your guess and the colors you got, e.g. `crane GYXXR` (G green, Y yellow, X gray), empty line to quit",
+        "输入您的猜测和得到的颜色, 例如 `crane GYXXR` (G 绿色, Y 黄色, X 灰色), 输入空行退出",
+    ),
+    ("cmd.correct", "CORRECT", "猜中了"),
+    (
+        "cmd.no_fit_final",
+        "no word in the final set fits the feedback",
+        "答案词库中没有符合反馈的词",
+    ),
+    (
+        "cmd.remaining",
+        "{} candidates remaining",
+        "剩余 {} 个候选词",
+    ),
+    ("cmd.suggestions", "suggestions:", "推荐:"),
+    (
+        "cmd.reverse.help",
+        "think of a word from the final set, then enter the colors for each of my guesses, e.g. `GYXXR`",
+        "请从答案词库中想好一个词, 然后为我的每次猜测输入颜色, 例如 `GYXXR`",
+    ),
+    (
+        "cmd.reverse.no_fit",
+        "no word in the final set fits your feedback",
+        "答案词库中没有符合您反馈的词",
+    ),
+    ("cmd.reverse.guess", "my guess {}: {}", "我的第 {} 次猜测: {}"),
+    (
+        "cmd.reverse.solved",
+        "I guessed your word in {} guesses!",
+        "我用 {} 次猜中了您的词!",
+    ),
+    (
+        "cmd.reverse.failed",
+        "I could not guess your word in {} guesses",
+        "{} 次内没有猜中您的词",
+    ),
+    ("cmd.replay.answer", "answer", "答案"),
+    ("cmd.replay.hard", "(hard)", "(困难)"),
+    // tui
+    ("ui.init.title", "Enter the word to be guessed!", "请输入指定猜测的词!"),
+    (
+        "ui.init.invalid",
+        "The word is not valid, press <Backspace> to enter it again! {}",
+        "输入的猜测词无效, 请按 <Backspace> 键重新输入猜测词! {}",
+    ),
+    (
+        "ui.init.typing",
+        "The word is not set or still being typed!",
+        "猜测词未设置或者正在输入中!",
+    ),
+    (
+        "ui.init.valid",
+        "The word is valid, press <Enter> to start!",
+        "输入的猜测词有效, 请按 <Enter> 键进入游戏!",
+    ),
+    ("ui.init.challenge", " Challenge code: {}", " 挑战码: {}"),
+    ("ui.status", "Status: ", "状态: "),
+    ("ui.assist.title", "Assistant", "助手模式"),
+    (
+        "ui.assist.solved",
+        "Solved, press <Enter> for a new game, <Esc> to quit!",
+        "已猜中, 按 <Enter> 键开始新的一局, 按 <Esc> 退出!",
+    ),
+    (
+        "ui.assist.help",
+        "Type a guess, press <1>-<5> to cycle letter colors, <Enter> to record, <Esc> to quit!",
+        "输入猜测词, 按 <1>-<5> 切换字母颜色, 按 <Enter> 键记录, 按 <Esc> 退出!",
+    ),
+    ("ui.reverse.title", "Reverse", "反向模式"),
+    (
+        "ui.reverse.solved",
+        "I guessed your word in {} tries, press <Enter> to play again, <Esc> to quit!",
+        "我用 {} 次猜中了您的词, 按 <Enter> 键再来一局, 按 <Esc> 退出!",
+    ),
+    (
+        "ui.reverse.failed",
+        "Could not guess it in {} tries, press <Enter> to play again, <Esc> to quit!",
+        "{} 次内没有猜中, 按 <Enter> 键再来一局, 按 <Esc> 退出!",
+    ),
+    (
+        "ui.reverse.help",
+        "Think of a word, press <1>-<5> to color my guess, <Enter> to confirm, <Esc> to quit!",
+        "请想好一个词, 按 <1>-<5> 为电脑的猜测切换颜色, 按 <Enter> 键确认, 按 <Esc> 退出!",
+    ),
+    ("ui.main.title", "Wordle", "猜词游戏"),
+    (
+        "ui.main.status",
+        "-> Status: [{}] Hard: [{}], Random: [{}] | ",
+        "-> 状态: [{}] 困难模式: [{}], 随机模式: [{}] | ",
+    ),
+    ("ui.yes", "yes", "是"),
+    ("ui.no", "no", "否"),
+    (
+        "ui.main.not_in_list",
+        "The guess [{}] is not in the word list!",
+        "输入的猜测词 [{}] 不在词库中!",
+    ),
+    (
+        "ui.main.success",
+        "Correct! Press <Enter> to continue, <Esc> to quit!",
+        "您猜对了, 请按 <Enter> 键继续, 按 <Esc> 退出游戏!",
+    ),
+    (
+        "ui.main.wrong",
+        "Guess {} is wrong, keep going!",
+        "第 {} 次猜测错误, 请继续!",
+    ),
+    ("ui.main.waiting", "Waiting for your guess!", "等待用户输入猜测词!"),
+    (
+        "ui.main.practice",
+        " | <Ctrl-U> undo <Ctrl-B> branches",
+        " | <Ctrl-U> 撤销 <Ctrl-B> 分支",
+    ),
+    ("ui.main.replaying", " | replaying", " | 回放中"),
+    ("ui.main.hint", " | hint: {}", " | 提示: {}"),
+    ("ui.main.hint_key", " | press <?> for a hint", " | 按 <?> 键获取提示"),
+    ("ui.main.hint_pending", " | thinking...", " | 正在计算提示..."),
+    ("ui.main.clock", " {} ", " {} "),
+    ("ui.main.paused", " {} paused ", " {} 已暂停 "),
+    (
+        "ui.too_small",
+        "Terminal too small: {}x{}, need at least {}x{}",
+        "终端太小: {}x{}, 至少需要 {}x{}",
+    ),
+    ("ui.popup.title", "Notice", "提示"),
+    (
+        "ui.gameover.help",
+        "Press <Enter> for a new game / <A> to review / <S> to copy the share text / <Esc> to quit, or click a button below!",
+        "按 <Enter> 键开始新游戏 / 按 <A> 键查看复盘 / 按 <S> 键复制分享文本 / 按 <Esc> 键退出游戏, 也可以点击下方按钮!",
+    ),
+    ("ui.button.new", "New game", "新游戏"),
+    ("ui.button.review", "Review", "复盘"),
+    ("ui.button.share", "Share", "分享"),
+    ("ui.button.quit", "Quit", "退出"),
+    ("ui.difficult.title", "Warning", "警告"),
+    (
+        "ui.difficult.contain",
+        "The word must contain: {}",
+        "必须包含以下字符: {}",
+    ),
+    (
+        "ui.difficult.position",
+        "These positions must match: {}",
+        "以下位置必须一一对应: {}",
+    ),
+    ("ui.difficult.continue", "Press <Enter> to continue", "按 <Enter> 键继续"),
+    ("ui.branches.title", "Branches", "分支"),
+    ("ui.branches.start", "(start) {} candidates", "(开局) {} 个候选"),
+    ("ui.branches.candidates", " {} candidates", " {} 个候选"),
+    (
+        "ui.branches.help",
+        "<↑>/<↓> select, <Enter> switch to the branch, <Esc> back",
+        "<↑>/<↓> 选择, <Enter> 切换到该分支, <Esc> 返回",
+    ),
+    (
+        "ui.branches.practice",
+        "branches are only available in practice mode",
+        "只有练习模式可以查看分支",
+    ),
+    ("ui.review.title", "Review", "复盘"),
+    ("ui.review.pending", "Analyzing the game...", "正在分析本局..."),
+    (
+        "ui.review.help",
+        "Press <Enter> to go back, <Esc> to quit!",
+        "按 <Enter> 键返回, 按 <Esc> 键退出游戏!",
+    ),
+    ("ui.candidates.title", "Candidates", "候选词"),
+    ("ui.candidates.remaining", "Candidates left: ", "剩余候选词: "),
+    (
+        "ui.candidates.none",
+        "No word fits the feedback, check the colors!",
+        "没有符合反馈的词, 请检查输入的颜色!",
+    ),
+    ("ui.candidates.suggest", "Suggestions: ", "推荐: "),
+    ("ui.guesses.title", "Guesses", "猜测词输入区"),
+    ("ui.keyboard.title", "Keyboard", "键盘区"),
+    ("ui.theme", "theme: {}", "主题: {}"),
+    (
+        "ui.main.screens",
+        " | <F1> help <F2> stats <F3> settings <:> commands",
+        " | <F1> 帮助 <F2> 统计 <F3> 设置 <:> 命令",
+    ),
+    ("ui.help.title", "Help", "帮助"),
+    (
+        "ui.help.rules",
+        "Guess the hidden 5-letter word in 6 tries. After each guess the tiles change color:",
+        "在 6 次内猜出隐藏的 5 个字母的单词. 每次猜测后方块会变色:",
+    ),
+    (
+        "ui.help.correct",
+        " the letter is in the word and in the right spot",
+        " 字母在单词中且位置正确",
+    ),
+    (
+        "ui.help.present",
+        " the letter is in the word but in the wrong spot",
+        " 字母在单词中但位置不对",
+    ),
+    (
+        "ui.help.absent",
+        " the letter is not in the word (or appears fewer times)",
+        " 字母不在单词中(或出现次数更少)",
+    ),
+    (
+        "ui.help.hard",
+        "Hard mode: revealed hints must be used in later guesses.",
+        "困难模式: 之后的猜测必须使用已揭示的提示.",
+    ),
+    (
+        "ui.help.back",
+        "Press <Enter> or <Esc> to go back",
+        "按 <Enter> 或 <Esc> 键返回",
+    ),
+    ("ui.help.key", "<{}> {}", "<{}> {}"),
+    ("ui.stats.title", "Statistics", "统计"),
+    (
+        "ui.stats.summary",
+        "Played: {}  Wins: {}  Win rate: {}%",
+        "总局数: {}  猜中: {}  胜率: {}%",
+    ),
+    ("ui.stats.distribution", "Guess distribution", "猜中次数分布"),
+    ("ui.settings.title", "Settings", "设置"),
+    ("ui.settings.hard", "Hard mode: {}", "困难模式: {}"),
+    ("ui.settings.theme", "Theme: {}", "主题: {}"),
+    ("ui.settings.length", "Word length: {}", "单词长度: {}"),
+    ("ui.settings.animations", "Animations: {}", "动画: {}"),
+    ("ui.settings.autocomplete", "Autocomplete: {}", "自动补全: {}"),
+    ("ui.settings.autocomplete.off", "off", "关闭"),
+    ("ui.settings.autocomplete.all", "all words", "所有单词"),
+    (
+        "ui.settings.autocomplete.consistent",
+        "possible answers",
+        "可能的答案",
+    ),
+    ("ui.completions.title", "Tab", "Tab"),
+    (
+        "ui.settings.length_note",
+        "Only 5-letter words are supported for now",
+        "目前只支持 5 个字母的单词",
+    ),
+    (
+        "ui.settings.next_game",
+        "Hard mode takes effect from the next game",
+        "困难模式从下一局开始生效",
+    ),
+    (
+        "ui.settings.help",
+        "<↑>/<↓> select, <Enter>/<←>/<→> change, <Esc> back",
+        "<↑>/<↓> 选择, <Enter>/<←>/<→> 切换, <Esc> 返回",
+    ),
+    ("keymap.submit", "submit", "提交"),
+    ("keymap.delete", "delete", "删除"),
+    ("keymap.quit", "quit", "退出"),
+    ("keymap.hint", "hint", "提示"),
+    ("keymap.share", "share", "分享"),
+    ("keymap.stats", "stats", "统计"),
+    ("keymap.help", "help", "帮助"),
+    ("keymap.settings", "settings", "设置"),
+    ("keymap.new_game", "new game", "新游戏"),
+    ("keymap.undo", "undo", "撤销"),
+    ("keymap.branches", "branches", "分支"),
+    ("keymap.theme", "theme", "主题"),
+    ("keymap.suspend", "suspend", "挂起"),
+    ("keymap.palette", "commands", "命令"),
+    ("keymap.complete", "complete", "补全"),
+    ("keymap.review", "review (after the game)", "复盘(结束后)"),
+    ("keymap.select_up", "previous", "上一项"),
+    ("keymap.select_down", "next", "下一项"),
+    (
+        "keymap.unknown_command",
+        "unknown keymap command `{}`",
+        "未知的按键命令 `{}`",
+    ),
+    (
+        "layout.unknown",
+        "unknown keyboard layout `{}`",
+        "未知的键盘布局 `{}`",
+    ),
+    ("keymap.invalid_key", "invalid key `{}`", "无效的按键 `{}`"),
+    (
+        "keymap.unknown_preset",
+        "unknown keymap preset `{}`",
+        "未知的按键预设 `{}`",
+    ),
+    (
+        "keymap.shadows_typing",
+        "`{}` is bound to `{}` and blocks typing that letter",
+        "`{}` 绑定到了 `{}`, 会挡住该字母的输入",
+    ),
+    (
+        "keymap.conflict",
+        "`{}` is bound to both `{}` and `{}`",
+        "`{}` 同时绑定到了 `{}` 和 `{}`",
+    ),
+    ("palette.unknown", "unknown command `{}`", "未知的命令 `{}`"),
+    ("palette.usage", "usage: :{}", "用法: :{}"),
+    (
+        "palette.invalid_seed",
+        "seed must be a number, got `{}`",
+        "种子必须是数字, 而不是 `{}`",
+    ),
+    (
+        "palette.not_final",
+        "`{}` is not in the final word list",
+        "`{}` 不在答案词库中",
+    ),
+    (
+        "ui.shared",
+        "Share text copied to the clipboard!",
+        "分享文本已复制到剪贴板!",
+    ),
+];
@@ -0,0 +1,395 @@
+//! 事件日志
+//!
+//! 一局游戏由一系列事件(开始, 输入字母, 提交猜测, 猜测被拒绝, 撤销, 结束)描述,
+//! [`Wordle`]的状态可以从事件重新推导. 用于练习模式的撤销, 精确回放,
+//! 崩溃后的自动恢复以及审计.
+//!
+//! 指定`--autosave`时每个事件以JSON-lines追加写入文件. 输入/删除字母只写入缓冲区,
+//! 提交, 拒绝, 撤销和结束时才刷新到磁盘. 读取时忽略崩溃导致的不完整的最后一行.
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Result, WordError},
+    pattern::Pattern,
+    record::now_millis,
+    t,
+    word::Word,
+    wordle::{CheckResult, Wordle, MAX_RETRY_TIMES},
+};
+
+/// 猜测被拒绝的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectReason {
+    // 不在词库中
+    NotInWordList,
+    // 违反困难模式规则
+    HardMode,
+}
+
+/// 游戏事件, `at`为Unix毫秒时间戳
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GameEvent {
+    Started {
+        answer: String,
+        hard: bool,
+        at: u64,
+    },
+    LetterTyped {
+        letter: char,
+        at: u64,
+    },
+    LetterRemoved {
+        at: u64,
+    },
+    GuessSubmitted {
+        word: String,
+        // 提交时计算的反馈, 如`GYRRG`, 用于审计
+        pattern: String,
+        at: u64,
+    },
+    GuessRejected {
+        word: String,
+        reason: RejectReason,
+        at: u64,
+    },
+    // 撤销最后一次猜测
+    Undone {
+        at: u64,
+    },
+    Ended {
+        won: bool,
+        at: u64,
+    },
+}
+
+impl GameEvent {
+    /// 改变了猜测结果的事件, 写入后需要立即刷新到磁盘
+    fn is_durable(&self) -> bool {
+        matches!(
+            self,
+            GameEvent::GuessSubmitted { .. }
+                | GameEvent::GuessRejected { .. }
+                | GameEvent::Undone { .. }
+                | GameEvent::Ended { .. }
+        )
+    }
+}
+
+/// 事件日志, 可选地自动保存到文件
+#[derive(Debug, Default)]
+pub struct Journal {
+    pub events: Vec<GameEvent>,
+    autosave: Option<PathBuf>,
+    // 打开的自动保存文件, 第一次写入时打开
+    writer: Option<BufWriter<File>>,
+}
+
+impl Clone for Journal {
+    /// 不共享打开的文件, 克隆的日志下次写入时重新打开
+    fn clone(&self) -> Self {
+        Journal {
+            events: self.events.clone(),
+            autosave: self.autosave.clone(),
+            writer: None,
+        }
+    }
+}
+
+impl Journal {
+    pub fn new(autosave: Option<PathBuf>) -> Self {
+        Journal {
+            events: vec![],
+            autosave,
+            writer: None,
+        }
+    }
+
+    /// 读取自动保存的日志, 忽略不完整的最后一行
+    pub fn load(path: &Path) -> Result<Vec<GameEvent>> {
+        let content = fs::read_to_string(path)?;
+        let lines = content.lines().collect::<Vec<_>>();
+        let mut events = vec![];
+        for (index, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(event) => events.push(event),
+                Err(_) if index + 1 == lines.len() => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(events)
+    }
+
+    /// 追加事件, 开启自动保存时写入文件
+    pub fn record(&mut self, event: GameEvent) -> Result<()> {
+        if let Some(ref path) = self.autosave {
+            if let GameEvent::Started { .. } = event {
+                // 新的一局覆盖之前的日志
+                self.writer = Some(BufWriter::new(File::create(path)?));
+            }
+            let writer = match self.writer {
+                Some(ref mut writer) => writer,
+                None => self.writer.insert(BufWriter::new(
+                    OpenOptions::new().create(true).append(true).open(path)?,
+                )),
+            };
+            serde_json::to_writer(&mut *writer, &event)?;
+            writeln!(writer)?;
+            if event.is_durable() {
+                writer.flush()?;
+                writer.get_ref().sync_data()?;
+            }
+        }
+        if let GameEvent::Started { .. } = event {
+            self.events.clear();
+        }
+        self.events.push(event);
+        Ok(())
+    }
+
+    /// 重写自动保存文件, 去掉不完整的行, 之后的事件继续写入该文件
+    fn rewrite(&mut self) -> Result<()> {
+        if let Some(ref path) = self.autosave {
+            let mut writer = BufWriter::new(File::create(path)?);
+            for event in self.events.iter() {
+                serde_json::to_writer(&mut writer, event)?;
+                writeln!(writer)?;
+            }
+            writer.flush()?;
+            writer.get_ref().sync_data()?;
+            self.writer = Some(writer);
+        }
+        Ok(())
+    }
+
+    /// 是否有已开始但未结束的一局
+    pub fn in_progress(&self) -> bool {
+        self.events
+            .iter()
+            .any(|e| matches!(e, GameEvent::Started { .. }))
+            && !matches!(self.events.last(), Some(GameEvent::Ended { .. }))
+    }
+}
+
+impl Wordle {
+    ///
+    /// 根据单个事件更新状态
+    ///
+    pub fn apply_event(&mut self, event: &GameEvent) -> Result<()> {
+        match event {
+            GameEvent::Started { answer, hard, .. } => {
+                self.final_word = Word::parse(answer)?;
+                self.opt.difficult = *hard;
+                self.clear();
+            }
+            GameEvent::LetterTyped { letter, .. } => self.states.current_word.push(*letter),
+            GameEvent::LetterRemoved { .. } => self.states.current_word.pop(),
+            GameEvent::GuessSubmitted { word, at, .. } => {
+                self.states.current_word = Word::parse(word)?;
+                let result = self.check_word();
+                if let Some(time) = self.guess_times.last_mut() {
+                    *time = *at;
+                }
+                self.states.current_checked_result = Some(result);
+                let last_try = self.states.current_try_times + 1 >= MAX_RETRY_TIMES;
+                if result == CheckResult::Wrong && !last_try {
+                    self.states.next_state();
+                }
+            }
+            GameEvent::GuessRejected { word, reason, .. } => {
+                self.states.current_word = Word::parse(word)?;
+                self.states.current_checked_result = Some(match reason {
+                    RejectReason::NotInWordList => CheckResult::InValid,
+                    RejectReason::HardMode => {
+                        self.resolve_difficult();
+                        CheckResult::Difficult
+                    }
+                });
+            }
+            GameEvent::Undone { .. } => {
+                if self.history_words.pop().is_some() {
+                    self.guess_times.pop();
+                    // 结束时最后一行没有增加猜测次数
+                    let try_times = if self.game_over {
+                        self.states.current_try_times
+                    } else {
+                        self.states.current_try_times.saturating_sub(1)
+                    };
+                    self.states.reset();
+                    self.states.current_try_times = try_times;
+                    self.cached_letter_states = self
+                        .history_words
+                        .iter()
+                        .flat_map(|w| w.get_letters().iter().copied())
+                        .collect();
+                    self.difficult_error_letters.clear();
+                    self.game_over = false;
+                }
+            }
+            GameEvent::Ended { .. } => self.game_over(),
+        }
+        Ok(())
+    }
+
+    ///
+    /// 更新状态并记录事件
+    ///
+    pub fn dispatch(&mut self, event: GameEvent) -> Result<()> {
+        self.apply_event(&event)?;
+        self.journal.record(event)
+    }
+
+    ///
+    /// 从事件重建状态
+    ///
+    pub fn restore(&mut self, events: &[GameEvent]) -> Result<()> {
+        self.clear();
+        for event in events {
+            self.apply_event(event)?;
+        }
+        self.journal.events = events.to_vec();
+        Ok(())
+    }
+
+    ///
+    /// 指定了`--autosave`且文件中有未结束的一局时恢复该局, 返回是否恢复
+    ///
+    pub fn resume_autosave(&mut self) -> Result<bool> {
+        let Some(path) = self.opt.autosave.clone() else {
+            return Ok(false);
+        };
+        self.journal = Journal::new(Some(path.clone()));
+        if !path.exists() {
+            return Ok(false);
+        }
+        let events = Journal::load(&path)?;
+        let journal = Journal {
+            events: events.clone(),
+            ..Default::default()
+        };
+        if !journal.in_progress() {
+            return Ok(false);
+        }
+        self.restore(&events)?;
+        self.journal.rewrite()?;
+        Ok(true)
+    }
+
+    ///
+    /// 以当前答案开始新的一局
+    ///
+    pub fn start_game(&mut self) -> Result<()> {
+        if self.journal.events.is_empty() && self.opt.autosave.is_some() {
+            self.journal = Journal::new(self.opt.autosave.clone());
+        }
+        self.dispatch(GameEvent::Started {
+            answer: self.final_word.to_string().to_lowercase(),
+            hard: self.opt.difficult,
+            at: now_millis(),
+        })
+    }
+
+    ///
+    /// 在当前行输入一个字母
+    ///
+    pub fn type_letter(&mut self, letter: char) -> Result<()> {
+        if self.is_game_over() || self.states.current_word.is_full() {
+            return Ok(());
+        }
+        self.dispatch(GameEvent::LetterTyped {
+            letter,
+            at: now_millis(),
+        })
+    }
+
+    ///
+    /// 删除当前行的最后一个字母
+    ///
+    pub fn remove_letter(&mut self) -> Result<()> {
+        if self.states.current_word.is_empty() {
+            return Ok(());
+        }
+        self.dispatch(GameEvent::LetterRemoved { at: now_millis() })
+    }
+
+    ///
+    /// 清空当前行并输入`word`
+    ///
+    pub fn type_word(&mut self, word: &str) -> Result<()> {
+        while !self.states.current_word.is_empty() {
+            self.remove_letter()?;
+        }
+        for letter in word.chars() {
+            self.type_letter(letter)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// 提交当前行, 猜中或用完次数时记录结束事件
+    ///
+    pub fn submit_guess(&mut self) -> Result<CheckResult> {
+        let word = self.states.current_word.to_string().to_lowercase();
+        let at = now_millis();
+        let reason = if !self.is_current_word_acceptable() && !self.is_current_word_final() {
+            Some(RejectReason::NotInWordList)
+        } else if !self.resolve_difficult() {
+            Some(RejectReason::HardMode)
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            self.dispatch(GameEvent::GuessRejected { word, reason, at })?;
+            return Ok(self
+                .states
+                .current_checked_result
+                .unwrap_or(CheckResult::InValid));
+        }
+        let mut guess = self.states.current_word.clone();
+        guess.diff(&self.final_word);
+        let states = guess.get_letters().iter().map(|l| l.1).collect::<Vec<_>>();
+        let pattern = Pattern::from_states(&states).to_string();
+        let last_try = self.states.current_try_times + 1 >= MAX_RETRY_TIMES;
+        self.dispatch(GameEvent::GuessSubmitted { word, pattern, at })?;
+        let result = self
+            .states
+            .current_checked_result
+            .unwrap_or(CheckResult::Wrong);
+        let won = result == CheckResult::Success;
+        if won || last_try {
+            self.dispatch(GameEvent::Ended { won, at })?;
+            // 练习模式可以撤销重来, 不计入统计
+            if !self.opt.practice {
+                self.statistics.record(won, self.history_words.len());
+                if let Some(ref path) = self.opt.state {
+                    self.statistics.save(path)?;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    ///
+    /// 练习模式下撤销最后一次猜测
+    ///
+    pub fn undo(&mut self) -> Result<()> {
+        if !self.opt.practice {
+            return Err(WordError::CustomError(
+                t!("error.undo_practice").to_string(),
+            ));
+        }
+        if self.history_words.is_empty() {
+            return Err(WordError::CustomError(
+                t!("error.nothing_to_undo").to_string(),
+            ));
+        }
+        self.dispatch(GameEvent::Undone { at: now_millis() })
+    }
+}
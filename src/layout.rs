@@ -0,0 +1,96 @@
+//! 键盘布局
+//!
+//! tui 的屏幕键盘和测试模式输出的键盘状态都按布局中字母的顺序排列.
+//! 内置 qwerty(默认), azerty, qwertz, dvorak, colemak 和按字母表顺序的布局;
+//! `auto` 按词库中出现的字母生成布局. 词库中有布局之外的字母时, 这些字母放在额外的一行.
+use std::collections::BTreeSet;
+
+use crate::{
+    error::{Result, WordError},
+    t,
+};
+
+/// 内置布局, 每个布局三行字母
+pub const LAYOUTS: [(&str, [&str; 3]); 6] = [
+    ("qwerty", ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"]),
+    ("azerty", ["AZERTYUIOP", "QSDFGHJKLM", "WXCVBN"]),
+    ("qwertz", ["QWERTZUIOP", "ASDFGHJKL", "YXCVBNM"]),
+    ("dvorak", ["PYFGCRL", "AOEUIDHTNS", "QJKXBMWVZ"]),
+    ("colemak", ["QWFPGJLUY", "ARSTDHNEIO", "ZXCVBKM"]),
+    ("alphabetical", ["ABCDEFGHIJ", "KLMNOPQRS", "TUVWXYZ"]),
+];
+
+// 生成布局时每行最多的字母数
+const ROW_LENGTH: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    pub name: String,
+    // 每行的字母, 均为大写
+    pub rows: Vec<Vec<char>>,
+}
+
+impl Default for KeyboardLayout {
+    fn default() -> Self {
+        KeyboardLayout::builtin("qwerty").expect("built-in keyboard layout")
+    }
+}
+
+impl KeyboardLayout {
+    pub fn builtin(name: &str) -> Option<Self> {
+        LAYOUTS
+            .iter()
+            .find(|(layout, _)| layout.eq_ignore_ascii_case(name))
+            .map(|(name, rows)| KeyboardLayout {
+                name: name.to_string(),
+                rows: rows.iter().map(|row| row.chars().collect()).collect(),
+            })
+    }
+
+    ///
+    /// 按字母表顺序生成布局, 字母平均分到每行不超过10个的若干行
+    ///
+    pub fn generate(name: &str, alphabet: impl IntoIterator<Item = char>) -> Self {
+        let letters = alphabet
+            .into_iter()
+            .filter(|ch| ch.is_alphabetic())
+            .flat_map(char::to_uppercase)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let count = letters.len().div_ceil(ROW_LENGTH).max(1);
+        let per_row = letters.len().div_ceil(count);
+        KeyboardLayout {
+            name: name.to_string(),
+            rows: letters
+                .chunks(per_row.max(1))
+                .map(<[char]>::to_vec)
+                .collect(),
+        }
+    }
+
+    ///
+    /// 按`--layout`选择布局, 未指定时为qwerty. 词库中布局之外的字母追加到额外的行
+    ///
+    pub fn resolve(name: Option<&str>, words: &[String]) -> Result<Self> {
+        let alphabet = words.iter().flat_map(|word| word.chars());
+        let mut layout = match name {
+            Some("auto") => return Ok(KeyboardLayout::generate("auto", alphabet)),
+            Some(name) => KeyboardLayout::builtin(name)
+                .ok_or_else(|| WordError::CustomError(t!("layout.unknown", name)))?,
+            None => KeyboardLayout::default(),
+        };
+        let keys = layout.keys();
+        let extra = KeyboardLayout::generate(
+            "",
+            alphabet.filter(|ch| ch.to_uppercase().all(|upper| !keys.contains(&upper))),
+        );
+        layout.rows.extend(extra.rows);
+        Ok(layout)
+    }
+
+    /// 按行依次排列的所有字母
+    pub fn keys(&self) -> Vec<char> {
+        self.rows.iter().flatten().copied().collect()
+    }
+}
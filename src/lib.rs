@@ -1,13 +1,30 @@
+pub mod analysis;
+pub mod arena;
+pub mod assistant;
+pub mod bench;
 pub mod buildin_words;
+pub mod challenge;
 // #[cfg(not(any(feature = "tui", feature = "gui")))]
 #[cfg(feature = "cmd")]
 pub mod cmd;
 pub mod command;
 pub mod error;
+pub mod explorer;
 #[cfg(feature = "gui")]
 pub mod gui;
+pub mod i18n;
+pub mod journal;
+pub mod layout;
+pub mod pattern;
+pub mod protocol;
+pub mod record;
+pub mod reverse;
+pub mod settings;
+pub mod share;
+pub mod solver;
 pub mod state;
 pub mod states;
+pub mod tree;
 #[cfg(feature = "tui")]
 pub mod tui;
 pub mod word;
@@ -0,0 +1,299 @@
+//! 反馈模式编码
+//!
+//! 每个猜测/答案对的反馈编码为一个三进制数(`R=0, Y=1, G=2`, 第`i`个字母为第`i`位),
+//! 5个字母共243种. 提供无内存分配的计算方法, 以及可并行构建并缓存到文件的
+//! 猜测×答案矩阵.
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    error::{Result, WordError},
+    state::LetterState,
+    t,
+    word::Word,
+};
+
+/// 反馈模式的数量
+pub const PATTERN_COUNT: usize = 243;
+
+// 缓存文件头
+const MAGIC: &[u8; 4] = b"WPM1";
+
+/// 一个猜测相对于答案的反馈
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Pattern(pub u8);
+
+impl Pattern {
+    /// 全部为绿色, 即猜中
+    pub const ALL_GREEN: Pattern = Pattern((PATTERN_COUNT - 1) as u8);
+
+    pub fn from_states(states: &[LetterState]) -> Self {
+        let mut code = 0;
+        for state in states.iter().rev() {
+            code = code * 3
+                + match state {
+                    LetterState::G => 2,
+                    LetterState::Y => 1,
+                    _ => 0,
+                };
+        }
+        Pattern(code)
+    }
+
+    pub fn states(&self) -> [LetterState; Word::MAX_LENGTH] {
+        let mut code = self.0;
+        let mut states = [LetterState::R; Word::MAX_LENGTH];
+        for state in states.iter_mut() {
+            *state = match code % 3 {
+                2 => LetterState::G,
+                1 => LetterState::Y,
+                _ => LetterState::R,
+            };
+            code /= 3;
+        }
+        states
+    }
+
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self == Self::ALL_GREEN
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for state in self.states() {
+            write!(f, "{:?}", state)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = WordError;
+
+    /// 解析用户输入的反馈, 如`GYXXR`: `G`绿色, `Y`黄色, `R`/`X`/`B`/`.`/`-`灰色,
+    /// 也支持🟩🟨⬛⬜
+    fn from_str(s: &str) -> Result<Self> {
+        let states = s
+            .trim()
+            .chars()
+            .map(|ch| match ch.to_ascii_uppercase() {
+                'G' | '🟩' => Ok(LetterState::G),
+                'Y' | '🟨' => Ok(LetterState::Y),
+                'R' | 'X' | 'B' | '.' | '-' | '⬛' | '⬜' => Ok(LetterState::R),
+                _ => Err(WordError::CustomError(t!("error.feedback_letter", ch))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if states.len() != Word::MAX_LENGTH {
+            return Err(WordError::CustomError(t!(
+                "error.feedback_length",
+                s.trim(),
+                Word::MAX_LENGTH
+            )));
+        }
+        Ok(Pattern::from_states(&states))
+    }
+}
+
+/// 计算`guess`相对于`answer`的反馈, 规则与[`Word::diff`]一致, 不区分大小写, 不分配内存.
+/// 非字母字符只会匹配为绿色
+pub fn score(guess: &str, answer: &str) -> Pattern {
+    let guess = guess.as_bytes();
+    let answer = answer.as_bytes();
+    let len = guess.len().min(answer.len()).min(Word::MAX_LENGTH);
+    // 未匹配为绿色的答案字母计数
+    let mut counts = [0u8; 26];
+    let mut digits = [0u8; Word::MAX_LENGTH];
+    for pos in 0..len {
+        if guess[pos].eq_ignore_ascii_case(&answer[pos]) {
+            digits[pos] = 2;
+        } else if let Some(index) = letter_index(answer[pos]) {
+            counts[index] += 1;
+        }
+    }
+    for pos in 0..len {
+        if digits[pos] != 0 {
+            continue;
+        }
+        if let Some(index) = letter_index(guess[pos]) {
+            if counts[index] > 0 {
+                counts[index] -= 1;
+                digits[pos] = 1;
+            }
+        }
+    }
+    Pattern(digits.iter().rev().fold(0, |code, digit| code * 3 + digit))
+}
+
+/// 字母在字母表中的序号, 不区分大小写, 非字母返回`None`
+fn letter_index(b: u8) -> Option<usize> {
+    let b = b.to_ascii_lowercase();
+    b.is_ascii_lowercase().then(|| (b - b'a') as usize)
+}
+
+/// FNV-1a哈希, 用于识别缓存对应的词库
+pub fn words_hash(guesses: &[String], answers: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+    for word in guesses {
+        feed(word.as_bytes());
+        feed(b"\n");
+    }
+    // 分隔猜测词库和答案词库
+    feed(b"\0");
+    for word in answers {
+        feed(word.as_bytes());
+        feed(b"\n");
+    }
+    hash
+}
+
+/// 猜测×答案的反馈矩阵
+#[derive(Debug, Clone)]
+pub struct PatternMatrix {
+    pub guesses: Vec<String>,
+    pub answers: Vec<String>,
+    data: Vec<u8>,
+    // 单词 -> 行号/列号
+    guess_index: HashMap<String, usize>,
+    answer_index: HashMap<String, usize>,
+}
+
+impl PatternMatrix {
+    fn new(guesses: &[String], answers: &[String], data: Vec<u8>) -> Self {
+        let index = |words: &[String]| {
+            words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| (word.clone(), i))
+                .collect()
+        };
+        PatternMatrix {
+            guesses: guesses.to_vec(),
+            answers: answers.to_vec(),
+            data,
+            guess_index: index(guesses),
+            answer_index: index(answers),
+        }
+    }
+
+    /// 并行计算所有反馈
+    pub fn build(guesses: &[String], answers: &[String]) -> Self {
+        let mut data = vec![0u8; guesses.len() * answers.len()];
+        if !answers.is_empty() {
+            data.par_chunks_mut(answers.len())
+                .zip(guesses.par_iter())
+                .for_each(|(row, guess)| {
+                    for (cell, answer) in row.iter_mut().zip(answers) {
+                        *cell = score(guess, answer).0;
+                    }
+                });
+        }
+        Self::new(guesses, answers, data)
+    }
+
+    /// 猜测词`word`所在的行
+    pub fn guess_index(&self, word: &str) -> Option<usize> {
+        self.guess_index.get(word).copied()
+    }
+
+    /// 答案`word`所在的列
+    pub fn answer_index(&self, word: &str) -> Option<usize> {
+        self.answer_index.get(word).copied()
+    }
+
+    /// 第`guess`个猜测词相对于第`answer`个答案的反馈
+    pub fn get(&self, guess: usize, answer: usize) -> Pattern {
+        Pattern(self.data[guess * self.answers.len() + answer])
+    }
+
+    /// 第`guess`个猜测词对所有答案的反馈
+    pub fn row(&self, guess: usize) -> &[u8] {
+        let width = self.answers.len();
+        &self.data[guess * width..(guess + 1) * width]
+    }
+
+    pub fn hash(&self) -> u64 {
+        words_hash(&self.guesses, &self.answers)
+    }
+
+    /// 缓存文件路径, 文件名包含词库的哈希
+    pub fn cache_path(dir: &Path, guesses: &[String], answers: &[String]) -> PathBuf {
+        dir.join(format!(
+            "patterns-{:016x}.bin",
+            words_hash(guesses, answers)
+        ))
+    }
+
+    /// 写入缓存文件
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&self.hash().to_le_bytes())?;
+        writer.write_all(&(self.guesses.len() as u32).to_le_bytes())?;
+        writer.write_all(&(self.answers.len() as u32).to_le_bytes())?;
+        writer.write_all(&self.data)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// 读取缓存文件, 词库与文件不一致时返回错误
+    pub fn load(path: &Path, guesses: &[String], answers: &[String]) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut header = [0u8; 20];
+        reader.read_exact(&mut header)?;
+        let hash = u64::from_le_bytes(header[4..12].try_into().unwrap());
+        let rows = u32::from_le_bytes(header[12..16].try_into().unwrap()) as usize;
+        let cols = u32::from_le_bytes(header[16..20].try_into().unwrap()) as usize;
+        if &header[0..4] != MAGIC
+            || hash != words_hash(guesses, answers)
+            || rows != guesses.len()
+            || cols != answers.len()
+        {
+            return Err(WordError::CustomError(format!(
+                "pattern cache `{}` does not match the word lists",
+                path.display()
+            )));
+        }
+        let mut data = Vec::with_capacity(rows * cols);
+        reader.read_to_end(&mut data)?;
+        if data.len() != rows * cols {
+            return Err(WordError::CustomError(format!(
+                "pattern cache `{}` is truncated",
+                path.display()
+            )));
+        }
+        Ok(Self::new(guesses, answers, data))
+    }
+
+    /// 优先从`dir`中读取缓存, 不存在或失效时重新计算并写入
+    pub fn load_or_build(dir: &Path, guesses: &[String], answers: &[String]) -> Result<Self> {
+        let path = Self::cache_path(dir, guesses, answers);
+        if let Ok(matrix) = Self::load(&path, guesses, answers) {
+            return Ok(matrix);
+        }
+        let matrix = Self::build(guesses, answers);
+        matrix.save(&path)?;
+        Ok(matrix)
+    }
+}
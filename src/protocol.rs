@@ -0,0 +1,249 @@
+//! JSON-lines 机器人协议
+//!
+//! 每行一个JSON对象. 输入为猜测命令, 输出为游戏事件:
+//!
+//! ```text
+//! -> {"cmd":"guess","word":"crane"}
+//! <- {"event":"feedback","attempt":1,"guess":"crane","pattern":"RYRRG",...}
+//! ```
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Result, WordError},
+    state::LetterState,
+    t,
+    word::Word,
+    wordle::{CheckResult, Wordle, MAX_RETRY_TIMES},
+};
+
+/// 输入输出协议
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Protocol {
+    // 默认的文本输出
+    #[default]
+    Text,
+    // JSON-lines
+    Json,
+}
+
+impl FromStr for Protocol {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(Protocol::Text),
+            "json" => Ok(Protocol::Json),
+            _ => Err(WordError::CustomError(t!("error.protocol", s))),
+        }
+    }
+}
+
+/// 机器人发送给游戏的命令
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum BotCommand {
+    Guess { word: String },
+    New,
+    Quit,
+}
+
+/// 错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    // 命令无法解析
+    BadCommand,
+    // 不在词库中
+    InvalidWord,
+    // 违反困难模式规则
+    HardMode,
+    // 游戏已结束
+    GameOver,
+    // 当前设置下不支持的命令
+    Unsupported,
+}
+
+/// 单个字母的反馈
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LetterFeedback {
+    pub letter: char,
+    pub state: LetterState,
+}
+
+/// 游戏发送给机器人的事件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum BotEvent {
+    Start {
+        word_length: usize,
+        max_guesses: u32,
+        hard: bool,
+    },
+    Feedback {
+        attempt: u32,
+        guess: String,
+        pattern: String,
+        letters: Vec<LetterFeedback>,
+        keyboard: BTreeMap<char, LetterState>,
+        // 为`true`时紧接着会发送`result`事件, 机器人无需再猜测
+        game_over: bool,
+    },
+    Error {
+        kind: ErrorKind,
+        message: String,
+    },
+    Result {
+        won: bool,
+        guesses: u32,
+        answer: String,
+    },
+}
+
+impl BotEvent {
+    fn error(kind: ErrorKind, message: impl Into<String>) -> Self {
+        BotEvent::Error {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+/// 将字母状态转换成 `GYRRG` 形式的字符串
+pub fn pattern_string(word: &Word) -> String {
+    word.get_letters()
+        .iter()
+        .map(|l| format!("{:?}", l.1))
+        .collect()
+}
+
+impl Wordle {
+    /// 当前游戏的开始事件
+    pub fn start_event(&self) -> BotEvent {
+        BotEvent::Start {
+            word_length: Word::MAX_LENGTH,
+            max_guesses: MAX_RETRY_TIMES,
+            hard: self.opt.difficult,
+        }
+    }
+
+    ///
+    /// 处理一条命令, 返回需要输出的事件列表
+    ///
+    pub fn handle_command(&mut self, command: BotCommand) -> Result<Vec<BotEvent>> {
+        match command {
+            BotCommand::Quit => {
+                self.exit = true;
+                Ok(vec![])
+            }
+            BotCommand::New => {
+                if self.opt.word.is_none() && !self.opt.random {
+                    return Ok(vec![BotEvent::error(
+                        ErrorKind::Unsupported,
+                        "new game requires `--word` or `--random`",
+                    )]);
+                }
+                // 随机模式下每局推进种子, 否则每局的答案都相同
+                if self.opt.random {
+                    self.opt.seed = Some(self.opt.seed.unwrap_or(2048).wrapping_add(1));
+                }
+                self.reset()?;
+                Ok(vec![self.start_event()])
+            }
+            BotCommand::Guess { word } => {
+                if self.is_game_over() {
+                    return Ok(vec![BotEvent::error(
+                        ErrorKind::GameOver,
+                        "game is over, send `new` or `quit`",
+                    )]);
+                }
+                let word = word.trim().to_lowercase();
+                let Ok(guess) = Word::parse(&word) else {
+                    return Ok(vec![BotEvent::error(
+                        ErrorKind::InvalidWord,
+                        WordError::InValidWord(word).to_string(),
+                    )]);
+                };
+                self.states.current_word = guess;
+                match self.check_word() {
+                    CheckResult::InValid => Ok(vec![BotEvent::error(
+                        ErrorKind::InvalidWord,
+                        WordError::InValidWord(word).to_string(),
+                    )]),
+                    CheckResult::Difficult => Ok(vec![BotEvent::error(
+                        ErrorKind::HardMode,
+                        self.difficult_error_messages().join("; "),
+                    )]),
+                    result => {
+                        self.states.current_try_times += 1;
+                        let won = result == CheckResult::Success;
+                        let game_over = won || self.states.current_try_times >= MAX_RETRY_TIMES;
+                        let current = &self.states.current_word;
+                        let mut events = vec![BotEvent::Feedback {
+                            attempt: self.states.current_try_times,
+                            guess: current.to_string(),
+                            pattern: pattern_string(current),
+                            letters: current
+                                .get_letters()
+                                .iter()
+                                .map(|l| LetterFeedback {
+                                    letter: l.0,
+                                    state: l.1,
+                                })
+                                .collect(),
+                            keyboard: self.keyboard_states(),
+                            game_over,
+                        }];
+                        if game_over {
+                            self.game_over();
+                            events.push(BotEvent::Result {
+                                won,
+                                guesses: self.states.current_try_times,
+                                answer: self.final_word.to_string(),
+                            });
+                        }
+                        self.states.current_word = Word::default();
+                        Ok(events)
+                    }
+                }
+            }
+        }
+    }
+
+    ///
+    /// 以JSON-lines协议运行游戏, 从`input`读取命令, 向`output`写入事件
+    ///
+    pub fn run_protocol<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> Result<()> {
+        write_event(&mut output, &self.start_event())?;
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let events = match serde_json::from_str::<BotCommand>(&line) {
+                Ok(command) => self.handle_command(command)?,
+                Err(e) => vec![BotEvent::error(ErrorKind::BadCommand, e.to_string())],
+            };
+            for event in events.iter() {
+                write_event(&mut output, event)?;
+            }
+            if self.exit {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 写入一行事件
+pub fn write_event<W: Write>(output: &mut W, event: &BotEvent) -> Result<()> {
+    serde_json::to_writer(&mut *output, event)?;
+    writeln!(output)?;
+    output.flush()?;
+    Ok(())
+}
@@ -0,0 +1,261 @@
+//! 对局记录
+//!
+//! 纯文本的对局记录格式, 包含设置, 答案以及每次猜测的时间戳和反馈:
+//!
+//! ```text
+//! [Game "Wordle"]
+//! [Answer "shire"]
+//! [Hard "false"]
+//! [WordLength "5"]
+//! [MaxGuesses "6"]
+//! [Result "2/6"]
+//!
+//! 1. crane RYRRG 1697040000123
+//! 2. shire GGGGG 1697040003456
+//! ```
+//!
+//! 时间戳为Unix毫秒. 读取后可以用[`Word::diff`]重新校验每次反馈.
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    error::{Result, WordError},
+    pattern::Pattern,
+    settings::WORD_LENGTHS,
+    t,
+    word::Word,
+    wordle::{Wordle, MAX_RETRY_TIMES},
+};
+
+/// 记录文件的扩展名
+pub const EXTENSION: &str = "wgn";
+
+/// 当前Unix时间(毫秒)
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// 一次猜测
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedGuess {
+    pub word: String,
+    pub pattern: Pattern,
+    pub timestamp: u64,
+}
+
+/// 一局游戏的记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub answer: String,
+    pub hard: bool,
+    pub word_length: usize,
+    pub max_guesses: u32,
+    pub guesses: Vec<RecordedGuess>,
+}
+
+impl GameRecord {
+    /// 是否猜中
+    pub fn solved(&self) -> bool {
+        self.guesses.last().is_some_and(|g| g.pattern.is_solved())
+    }
+
+    /// 成绩, 如`4/6`, 未猜中为`X/6`
+    pub fn result(&self) -> String {
+        if self.solved() {
+            format!("{}/{}", self.guesses.len(), self.max_guesses)
+        } else {
+            format!("X/{}", self.max_guesses)
+        }
+    }
+
+    /// 用[`Word::diff`]重新计算每次猜测的反馈, 与记录不一致或规则不受支持时返回错误
+    pub fn verify(&self) -> Result<()> {
+        if !WORD_LENGTHS.contains(&self.word_length) {
+            return Err(WordError::CustomError(t!(
+                "error.unsupported_length",
+                self.word_length,
+                Word::MAX_LENGTH
+            )));
+        }
+        if self.max_guesses != MAX_RETRY_TIMES {
+            return Err(WordError::CustomError(t!(
+                "error.unsupported_guesses",
+                self.max_guesses,
+                MAX_RETRY_TIMES
+            )));
+        }
+        let answer = Word::parse(&self.answer)?;
+        if self.guesses.len() > self.max_guesses as usize {
+            return Err(WordError::CustomError(t!(
+                "error.record_too_long",
+                self.guesses.len(),
+                self.max_guesses
+            )));
+        }
+        for (index, guess) in self.guesses.iter().enumerate() {
+            let mut word = Word::parse(&guess.word)?;
+            word.diff(&answer);
+            let states = word.get_letters().iter().map(|l| l.1).collect::<Vec<_>>();
+            let expected = Pattern::from_states(&states);
+            if expected != guess.pattern {
+                return Err(WordError::CustomError(t!(
+                    "error.record_mismatch",
+                    index + 1,
+                    guess.word,
+                    guess.pattern,
+                    expected
+                )));
+            }
+            if expected.is_solved() && index + 1 != self.guesses.len() {
+                return Err(WordError::CustomError(t!(
+                    "error.record_extra",
+                    index + 1,
+                    guess.word
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 读取记录文件
+    pub fn load(path: &Path) -> Result<Self> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// 写入`dir`, 文件名为第一次猜测的时间戳
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let started = self
+            .guesses
+            .first()
+            .map_or_else(now_millis, |g| g.timestamp);
+        let path = dir.join(format!("game-{}.{}", started, EXTENSION));
+        fs::write(&path, self.to_string())?;
+        Ok(path)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[Game \"Wordle\"]")?;
+        writeln!(f, "[Answer \"{}\"]", self.answer)?;
+        writeln!(f, "[Hard \"{}\"]", self.hard)?;
+        writeln!(f, "[WordLength \"{}\"]", self.word_length)?;
+        writeln!(f, "[MaxGuesses \"{}\"]", self.max_guesses)?;
+        writeln!(f, "[Result \"{}\"]", self.result())?;
+        writeln!(f)?;
+        for (index, guess) in self.guesses.iter().enumerate() {
+            writeln!(
+                f,
+                "{}. {} {} {}",
+                index + 1,
+                guess.word,
+                guess.pattern,
+                guess.timestamp
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |line: &str| WordError::CustomError(t!("error.record_line", line));
+        let mut answer = None;
+        let mut record = GameRecord {
+            answer: String::new(),
+            hard: false,
+            word_length: Word::MAX_LENGTH,
+            max_guesses: MAX_RETRY_TIMES,
+            guesses: vec![],
+        };
+        for line in s
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+        {
+            if let Some(tag) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let (name, value) = tag.split_once(' ').ok_or_else(|| invalid(line))?;
+                let value = value.trim().trim_matches('"');
+                match name {
+                    "Answer" => answer = Some(value.to_lowercase()),
+                    "Hard" => record.hard = value.parse().map_err(|_| invalid(line))?,
+                    "WordLength" => record.word_length = value.parse()?,
+                    "MaxGuesses" => record.max_guesses = value.parse()?,
+                    // 其他标签(如Result)由记录内容推导
+                    _ => {}
+                }
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let (Some(number), Some(word), Some(pattern), Some(timestamp), None) = (
+                parts.next(),
+                parts.next(),
+                parts.next(),
+                parts.next(),
+                parts.next(),
+            ) else {
+                return Err(invalid(line));
+            };
+            let number = number
+                .strip_suffix('.')
+                .and_then(|n| n.parse::<usize>().ok());
+            if number != Some(record.guesses.len() + 1) {
+                return Err(invalid(line));
+            }
+            record.guesses.push(RecordedGuess {
+                word: word.to_lowercase(),
+                pattern: pattern.parse()?,
+                timestamp: timestamp.parse()?,
+            });
+        }
+        record.answer = answer.ok_or(WordError::CustomError(
+            t!("error.record_answer").to_string(),
+        ))?;
+        Ok(record)
+    }
+}
+
+impl Wordle {
+    ///
+    /// 当前对局的记录
+    ///
+    pub fn record(&self) -> GameRecord {
+        GameRecord {
+            answer: self.final_word.to_string().to_lowercase(),
+            hard: self.opt.difficult,
+            word_length: Word::MAX_LENGTH,
+            max_guesses: MAX_RETRY_TIMES,
+            guesses: self
+                .history_words
+                .iter()
+                .zip(self.guess_times.iter())
+                .map(|(word, &timestamp)| RecordedGuess {
+                    word: word.to_string().to_lowercase(),
+                    pattern: Pattern::from_states(
+                        &word.get_letters().iter().map(|l| l.1).collect::<Vec<_>>(),
+                    ),
+                    timestamp,
+                })
+                .collect(),
+        }
+    }
+
+    ///
+    /// 指定了`--record`目录时写入本局记录
+    ///
+    pub fn save_record(&self) -> Result<Option<PathBuf>> {
+        match self.opt.record {
+            Some(ref dir) if !self.history_words.is_empty() => Ok(Some(self.record().save(dir)?)),
+            _ => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! 反向模式
+//!
+//! 玩家想好一个词, 由电脑猜测, 玩家给出每次猜测的颜色反馈.
+use crate::{
+    error::{Result, WordError},
+    pattern::Pattern,
+    solver::is_consistent,
+    state::LetterState,
+    t,
+    wordle::{Wordle, MAX_RETRY_TIMES},
+};
+
+impl Wordle {
+    ///
+    /// 电脑的下一个猜测, 没有符合反馈的词时返回`None`
+    ///
+    pub fn reverse_guess(&self) -> Option<String> {
+        if self.remaining_candidates().is_empty() {
+            return None;
+        }
+        self.hint()
+    }
+
+    ///
+    /// 记录玩家对`guess`给出的反馈, 返回剩余候选词数.
+    /// 反馈前后矛盾(所有可以猜测的词都不符合)时撤销本次记录并返回错误, 玩家可以修改颜色重新提交.
+    /// 反馈本身没有矛盾, 只是答案词库中没有符合的词时照常记录, 返回0, 之后`reverse_guess`返回`None`.
+    /// 猜中或用完猜测次数后游戏结束.
+    ///
+    pub fn reverse_feedback(&mut self, guess: &str, pattern: Pattern) -> Result<usize> {
+        self.record_feedback(guess, pattern)?;
+        let remaining = self.remaining_candidates().len();
+        if remaining == 0 && !self.feedback_satisfiable() {
+            self.undo_feedback();
+            return Err(WordError::CustomError(t!(
+                "error.reverse_inconsistent",
+                pattern
+            )));
+        }
+        if self.history_words.len() >= MAX_RETRY_TIMES as usize {
+            self.game_over = true;
+        }
+        Ok(remaining)
+    }
+
+    /// 可以猜测的词中是否有符合全部反馈的词
+    fn feedback_satisfiable(&self) -> bool {
+        self.acceptable_set
+            .iter()
+            .chain(&self.final_set)
+            .any(|word| is_consistent(&self.history_words, word))
+    }
+
+    ///
+    /// 最后一次反馈是否全部为绿色
+    ///
+    pub fn reverse_solved(&self) -> bool {
+        self.history_words
+            .last()
+            .is_some_and(|word| word.get_letters().iter().all(|l| l.1 == LetterState::G))
+    }
+}
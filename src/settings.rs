@@ -0,0 +1,109 @@
+//! 持久化的用户设置
+//!
+//! 设置保存在`--settings`指定的json文件中, 默认为`$XDG_CONFIG_HOME/wordle/settings.json`
+//! (未设置时为`~/.config/wordle/settings.json`). 命令行参数优先于保存的设置.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Result, word::Word};
+
+/// 可选的单词长度, 内置词库只有5个字母的单词
+pub const WORD_LENGTHS: [usize; 1] = [Word::MAX_LENGTH];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // 困难模式
+    pub hard: bool,
+    // tui配色主题名
+    pub theme: String,
+    pub word_length: usize,
+    // tui动画, 关闭后立即显示反馈
+    pub animations: bool,
+    // tui输入时的自动补全弹窗
+    pub autocomplete: Autocomplete,
+}
+
+/// 自动补全弹窗中列出的单词
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Autocomplete {
+    #[default]
+    Off,
+    // 所有可接受的单词, 困难模式下只列出满足规则的词
+    All,
+    // 只列出与已有反馈一致, 可能是答案的词
+    Consistent,
+}
+
+impl Autocomplete {
+    pub fn next(self) -> Self {
+        match self {
+            Autocomplete::Off => Autocomplete::All,
+            Autocomplete::All => Autocomplete::Consistent,
+            Autocomplete::Consistent => Autocomplete::Off,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            hard: false,
+            theme: "dark".to_string(),
+            word_length: Word::MAX_LENGTH,
+            animations: true,
+            autocomplete: Autocomplete::Off,
+        }
+    }
+}
+
+impl Settings {
+    /// 默认的设置文件路径
+    pub fn default_path() -> Option<PathBuf> {
+        let config = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(config.join("wordle").join("settings.json"))
+    }
+
+    ///
+    /// 读取设置, 文件不存在时使用默认设置. 不支持的单词长度换成默认长度
+    ///
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let mut settings: Settings = serde_json::from_str(&content)?;
+                if !WORD_LENGTHS.contains(&settings.word_length) {
+                    settings.word_length = Word::MAX_LENGTH;
+                }
+                Ok(settings)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Settings::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 切换到下一个可选的单词长度
+    pub fn next_word_length(&mut self) {
+        let index = WORD_LENGTHS
+            .iter()
+            .position(|length| *length == self.word_length)
+            .map_or(0, |index| (index + 1) % WORD_LENGTHS.len());
+        self.word_length = WORD_LENGTHS[index];
+    }
+}
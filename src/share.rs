@@ -0,0 +1,101 @@
+//! 分享文本
+//!
+//! 生成类似NYT的分享文本, 如:
+//!
+//! ```text
+//! Wordle 123 4/6*
+//!
+//! ⬛🟨⬛⬛⬛
+//! ⬛⬛🟩🟨⬛
+//! 🟩🟩🟩⬛🟩
+//! 🟩🟩🟩🟩🟩
+//! ```
+use crate::{
+    state::LetterState,
+    word::Word,
+    wordle::{Wordle, MAX_RETRY_TIMES},
+};
+
+/// 生成分享文本, `number`为局号(没有时省略), 困难模式在成绩后加`*`, 高对比度使用🟧🟦代替🟩🟨
+pub fn share_text(
+    number: Option<u64>,
+    history: &[Word],
+    hard: bool,
+    high_contrast: bool,
+) -> String {
+    let solved = history
+        .last()
+        .is_some_and(|word| word.get_letters().iter().all(|l| l.1 == LetterState::G));
+    let score = if solved {
+        history.len().to_string()
+    } else {
+        "X".to_string()
+    };
+    let number = number.map(|n| format!(" {}", n)).unwrap_or_default();
+    let mut text = format!(
+        "Wordle{} {}/{}{}\n",
+        number,
+        score,
+        MAX_RETRY_TIMES,
+        if hard { "*" } else { "" }
+    );
+    for word in history {
+        text.push('\n');
+        for letter in word.get_letters() {
+            text.push(match (letter.1, high_contrast) {
+                (LetterState::G, false) => '🟩',
+                (LetterState::G, true) => '🟧',
+                (LetterState::Y, false) => '🟨',
+                (LetterState::Y, true) => '🟦',
+                _ => '⬛',
+            });
+        }
+    }
+    text
+}
+
+/// 通过OSC 52转义序列将`text`复制到终端的剪贴板
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+impl Wordle {
+    ///
+    /// 本局的局号: 随机模式下为随机种子, 相同种子得到相同答案且不会泄露答案.
+    /// 指定答案时没有局号
+    ///
+    pub fn game_number(&self) -> Option<u64> {
+        self.opt.random.then(|| self.opt.seed.unwrap_or(2048))
+    }
+
+    ///
+    /// 本局的分享文本
+    ///
+    pub fn share_text(&self) -> String {
+        share_text(
+            self.game_number(),
+            &self.history_words,
+            self.opt.difficult,
+            self.opt.high_contrast,
+        )
+    }
+}
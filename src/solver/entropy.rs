@@ -0,0 +1,19 @@
+use super::{ranked_by, Context, Strategy};
+
+/// 选择反馈信息熵最大的猜测词
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Entropy;
+
+impl Strategy for Entropy {
+    fn name(&self) -> &'static str {
+        "entropy"
+    }
+
+    fn suggestions(&self, context: &Context<'_>, count: usize) -> Vec<String> {
+        let candidates = context.candidates();
+        let partitioner = context.partitioner(&candidates);
+        ranked_by(context, &candidates, count, |guess| {
+            -partitioner.entropy(guess)
+        })
+    }
+}
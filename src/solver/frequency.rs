@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+use super::{Context, Strategy};
+
+/// 根据候选词中字母出现的频率打分, 选择得分最高的候选词
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LetterFrequency;
+
+impl Strategy for LetterFrequency {
+    fn name(&self) -> &'static str {
+        "frequency"
+    }
+
+    fn suggestions(&self, context: &Context<'_>, count: usize) -> Vec<String> {
+        let candidates = context.candidates();
+        // 字母总频率及每个位置的频率
+        let mut total = [0usize; 26];
+        let mut positional = [[0usize; 26]; 5];
+        for word in candidates.iter() {
+            for (pos, b) in word.bytes().enumerate().take(5) {
+                positional[pos][index(b)] += 1;
+            }
+            for b in word.bytes().collect::<HashSet<_>>() {
+                total[index(b)] += 1;
+            }
+        }
+        let score = |word: &str| {
+            let unique = word.bytes().collect::<HashSet<_>>();
+            let letters = unique.iter().map(|b| total[index(*b)]).sum::<usize>();
+            let positions = word
+                .bytes()
+                .enumerate()
+                .take(5)
+                .map(|(pos, b)| positional[pos][index(b)])
+                .sum::<usize>();
+            letters + positions
+        };
+        let mut scored = candidates
+            .iter()
+            .map(|word| (score(word), *word))
+            .collect::<Vec<_>>();
+        // 分数从高到低, 相同时按原顺序
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored
+            .into_iter()
+            .take(count)
+            .map(|(_, word)| word.to_string())
+            .collect()
+    }
+}
+
+fn index(b: u8) -> usize {
+    b.wrapping_sub(b'a') as usize % 26
+}
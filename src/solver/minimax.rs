@@ -0,0 +1,24 @@
+use super::{ranked_by, Context, Strategy};
+
+/// 选择最坏情况下剩余候选词最少的猜测词
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Minimax;
+
+impl Strategy for Minimax {
+    fn name(&self) -> &'static str {
+        "minimax"
+    }
+
+    fn suggestions(&self, context: &Context<'_>, count: usize) -> Vec<String> {
+        let candidates = context.candidates();
+        let partitioner = context.partitioner(&candidates);
+        ranked_by(context, &candidates, count, |guess| {
+            partitioner
+                .partition(guess)
+                .iter()
+                .max()
+                .copied()
+                .unwrap_or(0) as f64
+        })
+    }
+}
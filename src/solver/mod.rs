@@ -0,0 +1,370 @@
+//! 求解策略
+//!
+//! 根据历史猜测和词库给出下一个猜测词, 供提示功能、基准测试和机器人使用.
+mod entropy;
+mod frequency;
+mod minimax;
+mod random;
+
+use std::{collections::HashSet, path::Path, str::FromStr};
+
+pub use entropy::Entropy;
+pub use frequency::LetterFrequency;
+pub use minimax::Minimax;
+pub use random::RandomConsistent;
+
+use crate::{
+    error::{Result, WordError},
+    pattern::{score, Pattern, PatternMatrix, PATTERN_COUNT},
+    state::LetterState,
+    t,
+    word::Word,
+    wordle::Wordle,
+};
+
+/// 求解时所需的游戏信息
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+    // 历史猜测, 包含每个字母的状态
+    pub history: &'a [Word],
+    pub acceptable_set: &'a [String],
+    pub final_set: &'a [String],
+    pub difficult: bool,
+    // 预先计算的反馈矩阵, 为空或不包含某个词时逐个计算
+    pub patterns: Option<&'a PatternMatrix>,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(wordle: &'a Wordle) -> Self {
+        Context {
+            history: &wordle.history_words,
+            acceptable_set: &wordle.acceptable_set,
+            final_set: &wordle.final_set,
+            difficult: wordle.opt.difficult,
+            patterns: wordle.patterns.as_deref(),
+        }
+    }
+
+    /// 与所有历史反馈一致的候选答案
+    pub fn candidates(&self) -> Vec<&'a str> {
+        self.final_set
+            .iter()
+            .map(|s| s.as_str())
+            .filter(|answer| is_consistent(self.history, answer))
+            .collect()
+    }
+
+    /// 可以作为猜测的词, 困难模式下只包含满足规则的词
+    pub fn guesses(&self) -> Vec<&'a str> {
+        let mut guesses = self
+            .acceptable_set
+            .iter()
+            .chain(self.final_set.iter())
+            .map(|s| s.as_str())
+            .filter(|guess| !self.difficult || satisfies_difficult(self.history, guess))
+            .collect::<Vec<_>>();
+        guesses.sort_unstable();
+        guesses.dedup();
+        guesses
+    }
+
+    /// 统计猜测对`candidates`的反馈分组, 有反馈矩阵时查表
+    pub fn partitioner<'b>(&self, candidates: &'b [&'b str]) -> Partitioner<'b>
+    where
+        'a: 'b,
+    {
+        Partitioner::new(candidates, self.patterns)
+    }
+}
+
+/// 对同一组候选答案统计不同猜测的反馈分组.
+/// 反馈矩阵包含所有候选答案时按列号查表, 否则逐个调用[`score`]
+#[derive(Debug, Clone)]
+pub struct Partitioner<'a> {
+    candidates: &'a [&'a str],
+    // 反馈矩阵和候选答案所在的列
+    lookup: Option<(&'a PatternMatrix, Vec<usize>)>,
+}
+
+impl<'a> Partitioner<'a> {
+    pub fn new(candidates: &'a [&'a str], patterns: Option<&'a PatternMatrix>) -> Self {
+        let lookup = patterns.and_then(|matrix| {
+            let columns = candidates
+                .iter()
+                .map(|answer| matrix.answer_index(answer))
+                .collect::<Option<Vec<_>>>()?;
+            Some((matrix, columns))
+        });
+        Partitioner { candidates, lookup }
+    }
+
+    /// 按反馈将候选词分组, 返回每种反馈模式下的候选词数
+    pub fn partition(&self, guess: &str) -> [usize; PATTERN_COUNT] {
+        let row = self
+            .lookup
+            .as_ref()
+            .and_then(|(matrix, columns)| Some((matrix.row(matrix.guess_index(guess)?), columns)));
+        let Some((row, columns)) = row else {
+            return partition(guess, self.candidates);
+        };
+        let mut buckets = [0; PATTERN_COUNT];
+        for &column in columns {
+            buckets[row[column] as usize] += 1;
+        }
+        buckets
+    }
+
+    /// 以`guess`猜测时反馈的信息熵(比特)
+    pub fn entropy(&self, guess: &str) -> f64 {
+        buckets_entropy(&self.partition(guess), self.candidates.len())
+    }
+}
+
+/// 求解策略
+pub trait Strategy: Send + Sync {
+    /// 策略名称
+    fn name(&self) -> &'static str;
+
+    /// 给出最多`count`个按推荐程度排序的猜测词
+    fn suggestions(&self, context: &Context<'_>, count: usize) -> Vec<String>;
+
+    /// 给出下一个猜测词, 没有可用的词时返回`None`
+    fn next_guess(&self, context: &Context<'_>) -> Option<String> {
+        self.suggestions(context, 1).into_iter().next()
+    }
+}
+
+/// 内置策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StrategyKind {
+    // 随机选择一个一致的候选词
+    Random,
+    // 字母频率
+    #[default]
+    Frequency,
+    // 最大化信息熵
+    Entropy,
+    // 最小化最坏情况下剩余的候选词数
+    Minimax,
+}
+
+impl StrategyKind {
+    pub const ALL: [StrategyKind; 4] = [
+        StrategyKind::Random,
+        StrategyKind::Frequency,
+        StrategyKind::Entropy,
+        StrategyKind::Minimax,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StrategyKind::Random => "random",
+            StrategyKind::Frequency => "frequency",
+            StrategyKind::Entropy => "entropy",
+            StrategyKind::Minimax => "minimax",
+        }
+    }
+
+    /// 是否逐对计算反馈, 此时预先计算[`PatternMatrix`]可以加速
+    pub fn uses_patterns(&self) -> bool {
+        matches!(self, StrategyKind::Entropy | StrategyKind::Minimax)
+    }
+
+    /// 创建策略, `seed`仅用于随机策略
+    pub fn build(&self, seed: u64) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::Random => Box::new(RandomConsistent::new(seed)),
+            StrategyKind::Frequency => Box::new(LetterFrequency),
+            StrategyKind::Entropy => Box::new(Entropy),
+            StrategyKind::Minimax => Box::new(Minimax),
+        }
+    }
+}
+
+impl FromStr for StrategyKind {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        StrategyKind::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names = StrategyKind::ALL.map(|kind| kind.name()).join(", ");
+                WordError::CustomError(t!("error.strategy", s, names))
+            })
+    }
+}
+
+/// 计算`guess`相对于`answer`的反馈, 规则与[`Word::diff`]一致
+pub fn feedback(guess: &str, answer: &str) -> [LetterState; Word::MAX_LENGTH] {
+    score(guess, answer).states()
+}
+
+/// `answer`是否与所有历史反馈一致
+pub fn is_consistent(history: &[Word], answer: &str) -> bool {
+    history.iter().all(|word| {
+        let states = word.get_letters().iter().map(|l| l.1).collect::<Vec<_>>();
+        score(&word.to_string().to_lowercase(), answer) == Pattern::from_states(&states)
+    })
+}
+
+/// 困难模式下`guess`是否满足规则: 绿色字母位置不变, 黄色字母必须出现
+pub fn satisfies_difficult(history: &[Word], guess: &str) -> bool {
+    let guess = guess.chars().collect::<Vec<_>>();
+    history.iter().all(|word| {
+        word.get_letters()
+            .iter()
+            .enumerate()
+            .all(|(index, letter)| match letter.1 {
+                LetterState::G => guess.get(index) == Some(&letter.0.to_ascii_lowercase()),
+                LetterState::Y => guess.contains(&letter.0.to_ascii_lowercase()),
+                _ => true,
+            })
+    })
+}
+
+/// 按反馈将候选词分组, 返回每种反馈模式下的候选词数
+pub fn partition(guess: &str, candidates: &[&str]) -> [usize; PATTERN_COUNT] {
+    let mut buckets = [0; PATTERN_COUNT];
+    for answer in candidates {
+        buckets[score(guess, answer).index()] += 1;
+    }
+    buckets
+}
+
+/// 以`guess`猜测时反馈的信息熵(比特), 即期望获得的信息量
+pub fn entropy(guess: &str, candidates: &[&str]) -> f64 {
+    buckets_entropy(&partition(guess, candidates), candidates.len())
+}
+
+fn buckets_entropy(buckets: &[usize; PATTERN_COUNT], total: usize) -> f64 {
+    let total = total as f64;
+    buckets
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// 依据`score`(越小越好)对所有可猜测词排序, 返回前`count`个, 分数相同时优先选择候选答案
+pub(crate) fn ranked_by<F>(
+    context: &Context<'_>,
+    candidates: &[&str],
+    count: usize,
+    score: F,
+) -> Vec<String>
+where
+    F: Fn(&str) -> f64,
+{
+    if candidates.len() <= 2 {
+        return candidates
+            .iter()
+            .take(count)
+            .map(|s| s.to_string())
+            .collect();
+    }
+    let candidate_set = candidates.iter().copied().collect::<HashSet<_>>();
+    let mut scored = context
+        .guesses()
+        .into_iter()
+        .map(|guess| (score(guess), !candidate_set.contains(guess), guess))
+        .collect::<Vec<_>>();
+    scored.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.cmp(b.2)));
+    scored
+        .into_iter()
+        .take(count)
+        .map(|(_, _, guess)| guess.to_string())
+        .collect()
+}
+
+/// 以`acceptable_set`和`final_set`的并集为猜测词, `final_set`为答案的反馈矩阵,
+/// 给出`cache`时优先读取其中的缓存
+pub fn pattern_matrix(
+    acceptable_set: &[String],
+    final_set: &[String],
+    cache: Option<&Path>,
+) -> Result<PatternMatrix> {
+    let mut guesses = acceptable_set.to_vec();
+    guesses.extend(final_set.iter().cloned());
+    guesses.sort();
+    guesses.dedup();
+    match cache {
+        Some(dir) => PatternMatrix::load_or_build(dir, &guesses, final_set),
+        None => Ok(PatternMatrix::build(&guesses, final_set)),
+    }
+}
+
+/// 使用`strategy`求解`answer`, 最多猜测`max_guesses`次, 返回包含反馈的猜测序列.
+/// 从空白历史开始, 忽略`context.history`
+pub fn solve(
+    strategy: &dyn Strategy,
+    context: Context<'_>,
+    answer: &str,
+    max_guesses: usize,
+) -> Vec<Word> {
+    let answer = Word::parse(answer).unwrap_or_default();
+    let mut history: Vec<Word> = vec![];
+    while history.len() < max_guesses {
+        let context = Context {
+            history: &history,
+            ..context
+        };
+        let Some(guess) = strategy.next_guess(&context) else {
+            break;
+        };
+        let Ok(mut word) = Word::parse(guess) else {
+            break;
+        };
+        word.diff(&answer);
+        let solved = word == answer;
+        history.push(word);
+        if solved {
+            break;
+        }
+    }
+    history
+}
+
+impl Wordle {
+    ///
+    /// 使用命令行指定的策略给出下一个猜测词
+    ///
+    pub fn hint(&self) -> Option<String> {
+        self.suggestions(1).into_iter().next()
+    }
+
+    ///
+    /// 使用命令行指定的策略给出最多`count`个推荐的猜测词
+    ///
+    pub fn suggestions(&self, count: usize) -> Vec<String> {
+        let strategy = self.opt.strategy.build(self.opt.seed.unwrap_or(2048));
+        strategy.suggestions(&Context::new(self), count)
+    }
+
+    ///
+    /// 以`prefix`开头的可以猜测的单词, 最多`count`个. `consistent`为真时只列出与已有反馈一致的词,
+    /// 困难模式下只列出满足规则的词
+    ///
+    pub fn completions(&self, prefix: &str, consistent: bool, count: usize) -> Vec<&str> {
+        let prefix = prefix.to_lowercase();
+        // 答案词库中的词也可以猜测
+        let mut words = self
+            .acceptable_set
+            .iter()
+            .chain(&self.final_set)
+            .map(String::as_str)
+            .filter(|word| word.starts_with(&prefix))
+            .collect::<Vec<_>>();
+        words.sort_unstable();
+        words.dedup();
+        words
+            .into_iter()
+            .filter(|word| !consistent || is_consistent(&self.history_words, word))
+            .filter(|word| !self.opt.difficult || satisfies_difficult(&self.history_words, word))
+            .take(count)
+            .collect()
+    }
+}
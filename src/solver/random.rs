@@ -0,0 +1,32 @@
+use rand::{seq::SliceRandom, SeedableRng};
+
+use super::{Context, Strategy};
+
+/// 从一致的候选词中随机选择
+#[derive(Debug, Clone, Copy)]
+pub struct RandomConsistent {
+    seed: u64,
+}
+
+impl RandomConsistent {
+    pub fn new(seed: u64) -> Self {
+        RandomConsistent { seed }
+    }
+}
+
+impl Strategy for RandomConsistent {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn suggestions(&self, context: &Context<'_>, count: usize) -> Vec<String> {
+        // 同一局中每一步使用不同的随机数
+        let seed = self.seed.wrapping_add(context.history.len() as u64);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        context
+            .candidates()
+            .choose_multiple(&mut rng, count)
+            .map(|s| s.to_string())
+            .collect()
+    }
+}
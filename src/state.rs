@@ -1,9 +1,13 @@
 use std::hash::Hash;
 
+use serde::{Deserialize, Serialize};
+
 ///
 /// 字母状态
 ///
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
 pub enum LetterState {
     // 绿色, 正确的字母
     G = 0,
@@ -69,3 +73,10 @@ pub enum Mode {
     #[cfg(feature = "gui")]
     Gui,
 }
+
+///
+/// 是否设置了`NO_COLOR`环境变量(非空即生效, 见 https://no-color.org)
+///
+pub fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
@@ -1,4 +1,4 @@
-use crate::{state::Letter, word::Word, wordle::CheckResult};
+use crate::{analysis::GameAnalysis, state::Letter, word::Word, wordle::CheckResult};
 use derive_builder::Builder;
 
 #[derive(Debug, Default, Clone, Builder)]
@@ -7,6 +7,22 @@ pub struct States {
     pub current_word_checked: bool,
     pub current_try_times: u32,
     pub current_checked_result: Option<CheckResult>,
+    // 求解策略给出的提示
+    pub hint: Option<String>,
+    // 提示正在后台计算
+    pub hint_pending: bool,
+    // 助手模式下推荐的猜测词
+    pub suggestions: Vec<String>,
+    // 状态栏显示的错误信息
+    pub message: Option<String>,
+    // 赛后分析, 进入复盘界面时计算
+    pub analysis: Option<GameAnalysis>,
+    // 赛后分析正在后台计算
+    pub analysis_pending: bool,
+    // 分支树中选中的节点
+    pub selected: usize,
+    // 自动补全弹窗中选中的单词
+    pub completion: Option<usize>,
 }
 
 impl States {
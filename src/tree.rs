@@ -0,0 +1,275 @@
+//! 决策树生成
+//!
+//! 给定开局词, 对`final_set`中的所有答案计算完整的策略树: 每种反馈对应下一个猜测词.
+//! 以平均猜测次数为优化目标, 优先保证所有答案都能在`MAX_RETRY_TIMES`次内猜中.
+//! 每个节点只搜索信息熵最高的若干候选词, 因此结果是启发式最优.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{Result, WordError},
+    pattern::{Pattern, PatternMatrix, PATTERN_COUNT},
+    wordle::MAX_RETRY_TIMES,
+};
+
+/// 导出的决策树节点
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionTree {
+    // 当前节点的猜测词
+    pub guess: String,
+    // 到达该节点时剩余的候选答案数
+    pub size: usize,
+    // 从该节点开始猜中所有候选答案所需的猜测总数
+    pub total_guesses: usize,
+    // 最坏情况下从该节点开始所需的猜测次数
+    pub depth: usize,
+    // 反馈(如`GYRRR`) -> 下一个节点, 猜中的反馈不包含在内
+    pub children: BTreeMap<String, DecisionTree>,
+}
+
+impl DecisionTree {
+    /// 平均猜测次数
+    pub fn average(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        self.total_guesses as f64 / self.size as f64
+    }
+
+    /// 是否所有答案都能在`MAX_RETRY_TIMES`次内猜中
+    pub fn within_limit(&self) -> bool {
+        self.depth <= MAX_RETRY_TIMES as usize
+    }
+
+    /// 以缩进文本形式输出
+    pub fn to_text(&self) -> String {
+        let mut out = format!(
+            "{} ({} answers, average {:.4}, worst {})\n",
+            self.guess,
+            self.size,
+            self.average(),
+            self.depth
+        );
+        self.write_children(&mut out, 1);
+        out
+    }
+
+    fn write_children(&self, out: &mut String, level: usize) {
+        for (pattern, child) in self.children.iter() {
+            out.push_str(&format!(
+                "{}{} {:>4} -> {}\n",
+                "  ".repeat(level),
+                pattern,
+                child.size,
+                child.guess
+            ));
+            child.write_children(out, level + 1);
+        }
+    }
+}
+
+/// 搜索中的节点
+#[derive(Debug, Clone)]
+struct Node {
+    guess: usize,
+    size: usize,
+    total: usize,
+    depth: usize,
+    children: Vec<(Pattern, Node)>,
+}
+
+impl Node {
+    /// 比较用的代价: 先比较是否超出剩余次数, 再比较猜测总数
+    fn cost(&self, budget: usize) -> (bool, usize) {
+        (self.depth > budget, self.total)
+    }
+}
+
+/// 决策树搜索
+pub struct TreeBuilder<'a> {
+    matrix: &'a PatternMatrix,
+    // 答案在猜测词库中的索引
+    answer_guess: Vec<usize>,
+    // 每个节点搜索的候选猜测词数量, 不是穷举搜索, 结果是启发式最优
+    breadth: usize,
+    memo: HashMap<(Vec<u16>, usize), Node>,
+}
+
+impl<'a> TreeBuilder<'a> {
+    /// `matrix`的猜测词库必须包含所有答案
+    pub fn new(matrix: &'a PatternMatrix, breadth: usize) -> Result<Self> {
+        let index = matrix
+            .guesses
+            .iter()
+            .enumerate()
+            .map(|(i, g)| (g.as_str(), i))
+            .collect::<HashMap<_, _>>();
+        let answer_guess = matrix
+            .answers
+            .iter()
+            .map(|a| {
+                index
+                    .get(a.as_str())
+                    .copied()
+                    .ok_or_else(|| WordError::InValidWord(a.to_string()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(TreeBuilder {
+            matrix,
+            answer_guess,
+            breadth: breadth.max(1),
+            memo: HashMap::new(),
+        })
+    }
+
+    /// 以`opener`为开局词生成决策树
+    pub fn build(&mut self, opener: &str) -> Result<DecisionTree> {
+        let guess = self
+            .matrix
+            .guesses
+            .iter()
+            .position(|g| g == opener)
+            .ok_or_else(|| WordError::InValidWord(opener.to_string()))?;
+        let all = (0..self.matrix.answers.len() as u16).collect::<Vec<_>>();
+        let node = self.evaluate(&all, guess, MAX_RETRY_TIMES as usize, None);
+        Ok(self.export(&node))
+    }
+
+    fn buckets(&self, set: &[u16], guess: usize) -> Vec<(Pattern, Vec<u16>)> {
+        let row = self.matrix.row(guess);
+        let mut buckets: Vec<Vec<u16>> = vec![vec![]; PATTERN_COUNT];
+        for &answer in set {
+            buckets[row[answer as usize] as usize].push(answer);
+        }
+        buckets
+            .into_iter()
+            .enumerate()
+            .filter(|(_, bucket)| !bucket.is_empty())
+            .map(|(code, bucket)| (Pattern(code as u8), bucket))
+            .collect()
+    }
+
+    /// 计算以`guess`猜测`set`时的子树, 总猜测数超过`bound`时提前放弃
+    fn evaluate(&mut self, set: &[u16], guess: usize, budget: usize, bound: Option<usize>) -> Node {
+        let mut node = Node {
+            guess,
+            size: set.len(),
+            total: set.len(),
+            depth: 1,
+            children: vec![],
+        };
+        for (pattern, bucket) in self.buckets(set, guess) {
+            if pattern.is_solved() {
+                continue;
+            }
+            let child = self.solve(&bucket, budget.saturating_sub(1));
+            node.total += child.total;
+            node.depth = node.depth.max(child.depth + 1);
+            node.children.push((pattern, child));
+            if bound.is_some_and(|bound| node.total > bound) {
+                break;
+            }
+        }
+        node
+    }
+
+    /// 选择需要搜索的候选猜测词: 信息熵最高的若干个, 以及最好的候选答案
+    fn shortlist(&self, set: &[u16]) -> Vec<usize> {
+        let total = set.len() as f64;
+        let entropy = |guess: usize| {
+            let row = self.matrix.row(guess);
+            let mut counts = [0usize; PATTERN_COUNT];
+            for &answer in set {
+                counts[row[answer as usize] as usize] += 1;
+            }
+            counts
+                .iter()
+                .filter(|&&n| n > 0)
+                .map(|&n| {
+                    let p = n as f64 / total;
+                    -p * p.log2()
+                })
+                .sum::<f64>()
+        };
+        let candidates = set
+            .iter()
+            .map(|&a| self.answer_guess[a as usize])
+            .collect::<HashSet<_>>();
+        // 候选答案较少时, 只考虑候选答案
+        let pool = if set.len() <= 3 {
+            candidates.iter().copied().collect()
+        } else {
+            (0..self.matrix.guesses.len()).collect::<Vec<_>>()
+        };
+        // 不能区分任何候选答案的词没有意义
+        let mut scored = pool
+            .into_iter()
+            .map(|g| (entropy(g), candidates.contains(&g), g))
+            .filter(|(entropy, is_candidate, _)| *entropy > 0.0 || *is_candidate)
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+        let mut shortlist = scored
+            .iter()
+            .take(self.breadth)
+            .map(|(_, _, g)| *g)
+            .collect::<Vec<_>>();
+        if let Some((_, _, best)) = scored.iter().find(|(_, is_candidate, _)| *is_candidate) {
+            if !shortlist.contains(best) {
+                shortlist.push(*best);
+            }
+        }
+        shortlist
+    }
+
+    /// 在剩余`budget`次猜测内求`set`的子树, 只在[`Self::shortlist`]的候选词中取最优
+    fn solve(&mut self, set: &[u16], budget: usize) -> Node {
+        let key = (set.to_vec(), budget);
+        if let Some(node) = self.memo.get(&key) {
+            return node.clone();
+        }
+        let node = if set.len() <= 2 {
+            // 猜其中一个, 另一个最多再猜1次
+            let guess = self.answer_guess[set[0] as usize];
+            self.evaluate(set, guess, budget, None)
+        } else {
+            // 1个猜中, 其余至少需要2次
+            let lower_bound = 2 * set.len() - 1;
+            let mut best: Option<Node> = None;
+            for guess in self.shortlist(set) {
+                // 只有已找到不超出次数的方案时才能剪枝
+                let bound = best.as_ref().filter(|b| b.depth <= budget).map(|b| b.total);
+                let node = self.evaluate(set, guess, budget, bound);
+                if best
+                    .as_ref()
+                    .is_none_or(|b| node.cost(budget) < b.cost(budget))
+                {
+                    best = Some(node);
+                }
+                if best
+                    .as_ref()
+                    .is_some_and(|b| b.depth <= budget && b.total == lower_bound)
+                {
+                    break;
+                }
+            }
+            best.unwrap()
+        };
+        self.memo.insert(key, node.clone());
+        node
+    }
+
+    fn export(&self, node: &Node) -> DecisionTree {
+        DecisionTree {
+            guess: self.matrix.guesses[node.guess].clone(),
+            size: node.size,
+            total_guesses: node.total,
+            depth: node.depth,
+            children: node
+                .children
+                .iter()
+                .map(|(pattern, child)| (pattern.to_string(), self.export(child)))
+                .collect(),
+        }
+    }
+}
@@ -1,10 +1,29 @@
-use crossterm::event::KeyCode;
+use std::io::{self, Write};
+use std::path::Path;
 
-use crate::error::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tokio::{
+    runtime::Handle,
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+
+use crate::analysis::GameAnalysis;
+use crate::command::Opt;
+use crate::error::{Result, WordError};
+use crate::pattern::Pattern;
+use crate::record::GameRecord;
+use crate::settings::Autocomplete;
+use crate::share::osc52;
+use crate::state::LetterState;
+use crate::t;
 use crate::word::Word;
-use crate::wordle::{Wordle, MAX_RETRY_TIMES};
+use crate::wordle::{CheckResult, Wordle, MAX_RETRY_TIMES};
 
-use super::ui::MainState;
+use super::animation::{Animation, AnimationKind};
+use super::clock::Clock;
+use super::keymap::{Command, Scope};
+use super::palette::{Palette, PaletteCommand};
+use super::ui::{completions, MainState};
 use super::{event::Event, ui::UiState};
 
 #[derive(Debug, Clone)]
@@ -15,27 +34,183 @@ pub enum Action {
     InputChar(char),
     PopUp,
     RemoveChar,
+    // 显示求解策略给出的提示
+    Hint,
+    // 后台计算出的提示, 附带计算时的历史猜测, 与当前不一致时丢弃
+    HintReady(Vec<Word>, Option<String>),
+    // 助手模式和反向模式下切换第n个字母的颜色
+    CycleState(usize),
     // 回车键
     Enter,
     // 进入MainState::Main
     EnterMain,
+    // 进入复盘界面
+    Review,
+    // 后台计算出的赛后分析, 附带计算时的历史猜测, 与当前不一致时丢弃
+    AnalysisReady(Vec<Word>, GameAnalysis),
+    // 复制分享文本到剪贴板
+    Share,
+    // 练习模式下撤销最后一次猜测
+    Undo,
+    // 练习模式下打开分支树
+    Branches,
+    // 分支树中选择上一个/下一个节点
+    SelectUp,
+    SelectDown,
+    // 切换到下一个内置配色主题
+    SwitchTheme,
+    // 打开帮助, 统计和设置界面
+    Help,
+    Stats,
+    Settings,
+    // 设置界面中切换选中的设置项
+    ChangeSetting,
+    // 终端大小变化, 立即重绘
+    Resize(u16, u16),
+    // 终端失去/获得焦点时暂停/恢复计时
+    FocusLost,
+    FocusGained,
+    // Ctrl-Z 挂起到后台
+    Suspend,
     ReNew,
+    // 放弃当前一局, 开始新的一局
+    NewGame,
+    // 打开命令面板, 面板中输入, 删除, 补全, 执行和关闭
+    Palette,
+    PaletteInput(char),
+    PaletteDelete,
+    PaletteComplete,
+    PaletteRun,
+    PaletteClose,
+    // 自动补全弹窗中选择下一个/上一个单词, 填入选中的单词, 取消选择
+    CompletionNext,
+    CompletionPrev,
+    CompletionAccept,
+    CompletionCancel,
     Error,
     Quit,
     None,
 }
 
 pub fn get_action(wordle: &Wordle, event: Event) -> Action {
+    if let Event::Key(key) = event {
+        if let Some(command @ (Command::Theme | Command::Suspend)) =
+            wordle.keymap.command(key, Scope::Global)
+        {
+            return command.action();
+        }
+    }
+    // 命令面板打开时接收所有按键, 未加修饰键的字符都是输入
+    if let (Some(palette), Event::Key(key)) = (&wordle.palette, event) {
+        if let KeyCode::Char(ch) = key.code {
+            if is_typing(key) {
+                return Action::PaletteInput(ch);
+            }
+        }
+        return match wordle.keymap.command(key, Scope::Main) {
+            Some(Command::Submit) => Action::PaletteRun,
+            Some(Command::Quit) => Action::PaletteClose,
+            Some(Command::Complete) => Action::PaletteComplete,
+            Some(Command::Delete) if palette.input.is_empty() => Action::PaletteClose,
+            Some(Command::Delete) => Action::PaletteDelete,
+            _ => Action::None,
+        };
+    }
+    // 自动补全弹窗: 补全和上下选择键选择, 选中后提交键填入当前行, 退出键取消选择
+    if let (UiState::Main(MainState::Main), Event::Key(key)) = (wordle.ui_state, event) {
+        let enabled = wordle.settings.autocomplete != Autocomplete::Off;
+        let selected = wordle.states.completion.is_some();
+        // 列表界面的选择键可以是字母, 输入界面中只使用不挡住输入的按键
+        let list = wordle
+            .keymap
+            .command(key, Scope::List)
+            .filter(|_| !is_typing(key));
+        match (wordle.keymap.command(key, Scope::Main), list) {
+            (Some(Command::Complete), _) | (None, Some(Command::SelectDown)) if enabled => {
+                return Action::CompletionNext
+            }
+            (None, Some(Command::SelectUp)) if enabled => return Action::CompletionPrev,
+            (None, _) if enabled && key.code == KeyCode::BackTab => return Action::CompletionPrev,
+            (Some(Command::Submit), _) if selected => return Action::CompletionAccept,
+            (Some(Command::Quit), _) if selected => return Action::CompletionCancel,
+            _ => {}
+        }
+    }
+    if let (UiState::Assist, Event::Key(key)) = (wordle.ui_state, event) {
+        return match (key.code, wordle.keymap.command(key, Scope::Main)) {
+            (KeyCode::Char(ch), _) if ch.is_ascii_alphabetic() && is_typing(key) => {
+                Action::InputChar(ch)
+            }
+            (KeyCode::Char(ch @ '1'..='5'), _) => Action::CycleState(ch as usize - '1' as usize),
+            (_, Some(Command::Delete)) => Action::RemoveChar,
+            (_, Some(Command::Submit)) => Action::Enter,
+            (_, Some(Command::Quit)) => Action::Quit,
+            _ => Action::None,
+        };
+    }
+    if let (UiState::Reverse, Event::Key(key)) = (wordle.ui_state, event) {
+        return match (key.code, wordle.keymap.command(key, Scope::Main)) {
+            (KeyCode::Char(ch @ '1'..='5'), _) => Action::CycleState(ch as usize - '1' as usize),
+            (_, Some(Command::Submit)) => Action::Enter,
+            (_, Some(Command::Quit)) => Action::Quit,
+            _ => Action::None,
+        };
+    }
+    if let (UiState::Main(MainState::Help | MainState::Stats), Event::Key(key)) =
+        (wordle.ui_state, event)
+    {
+        return match wordle.keymap.command(key, Scope::Main) {
+            Some(
+                Command::Submit
+                | Command::Quit
+                | Command::Help
+                | Command::Stats
+                | Command::Settings,
+            ) => Action::EnterMain,
+            _ => Action::None,
+        };
+    }
+    if let (UiState::Main(MainState::Settings), Event::Key(key)) = (wordle.ui_state, event) {
+        if let Some(command) = wordle.keymap.command(key, Scope::List) {
+            return command.action();
+        }
+        return match (key.code, wordle.keymap.command(key, Scope::Main)) {
+            (KeyCode::Left | KeyCode::Right | KeyCode::Char(' '), _)
+            | (_, Some(Command::Submit)) => Action::ChangeSetting,
+            (_, Some(Command::Quit | Command::Help | Command::Stats | Command::Settings)) => {
+                Action::EnterMain
+            }
+            _ => Action::None,
+        };
+    }
+    if let (UiState::Main(MainState::Branches), Event::Key(key)) = (wordle.ui_state, event) {
+        if let Some(command) = wordle.keymap.command(key, Scope::List) {
+            return command.action();
+        }
+        return match wordle.keymap.command(key, Scope::Main) {
+            Some(Command::Submit) => Action::Enter,
+            Some(Command::Quit) => Action::EnterMain,
+            _ => Action::None,
+        };
+    }
+    // 结束弹窗中先查找弹窗的命令
+    let scope = if wordle.ui_state == UiState::Main(MainState::GameOver) {
+        Scope::Popup
+    } else {
+        Scope::Main
+    };
     match event {
         Event::Init if wordle.final_word.is_empty() => Action::Init,
         Event::Quit => Action::Quit,
         Event::Tick => Action::Tick,
         Event::Render => Action::Render,
-        Event::Key(key) => match key.code {
-            KeyCode::Char(ch) if ch.is_alphabetic() => Action::InputChar(ch),
-            KeyCode::Backspace => Action::RemoveChar,
-            KeyCode::Enter => match wordle.ui_state {
+        Event::Resize(width, height) => Action::Resize(width, height),
+        Event::FocusLost => Action::FocusLost,
+        Event::FocusGained => Action::FocusGained,
+        Event::Key(key) => match (key.code, wordle.keymap.command(key, scope)) {
+            (_, Some(Command::Submit)) => match wordle.ui_state {
                 UiState::Init => Action::Init,
+                UiState::Assist | UiState::Reverse => Action::Enter,
                 UiState::Main(main_state) => match main_state {
                     MainState::Main => {
                         if !wordle.is_game_over() {
@@ -45,24 +220,113 @@ pub fn get_action(wordle: &Wordle, event: Event) -> Action {
                     }
                     MainState::Difficult => Action::EnterMain,
                     MainState::GameOver => Action::ReNew,
+                    MainState::Review => Action::PopUp,
+                    MainState::Branches => Action::Enter,
+                    MainState::Help | MainState::Stats => Action::EnterMain,
+                    MainState::Settings => Action::ChangeSetting,
                 },
             },
-            KeyCode::Esc => Action::Quit,
+            (_, Some(command)) => command.action(),
+            (KeyCode::Char(ch), None) if ch.is_alphabetic() && is_typing(key) => {
+                Action::InputChar(ch)
+            }
             _ => Action::None,
         },
         _ => Action::None,
     }
 }
 
+///
+/// 后台计算的结果通道. 保存在`Wordle`中, 挂起后重新进入事件循环时不会丢失结果
+///
+#[derive(Debug)]
+pub struct Jobs {
+    tx: UnboundedSender<Action>,
+    rx: UnboundedReceiver<Action>,
+}
+
+impl Default for Jobs {
+    fn default() -> Self {
+        let (tx, rx) = unbounded_channel();
+        Jobs { tx, rx }
+    }
+}
+
+impl Jobs {
+    /// 取出一个已经完成的结果
+    pub fn try_recv(&mut self) -> Option<Action> {
+        self.rx.try_recv().ok()
+    }
+}
+
+///
+/// 在阻塞线程池中执行`job`, 完成后把得到的action交给事件循环, 不阻塞界面.
+/// 不在tokio运行时中(如同步测试直接调用`update`)时就地执行
+///
+fn run_blocking<F>(wordle: &mut Wordle, job: F) -> Result<()>
+where
+    F: FnOnce() -> Action + Send + 'static,
+{
+    match Handle::try_current() {
+        Ok(handle) => {
+            let tx = wordle.jobs.tx.clone();
+            handle.spawn_blocking(move || {
+                // 游戏已经退出时丢弃结果
+                let _ = tx.send(job());
+            });
+            Ok(())
+        }
+        Err(_) => update(wordle, job()),
+    }
+}
+
+/// 后台求解和赛后分析所需的游戏状态副本: 历史猜测, 词库, 策略和反馈矩阵
+fn solver_snapshot(wordle: &Wordle) -> Wordle {
+    Wordle {
+        history_words: wordle.history_words.clone(),
+        acceptable_set: wordle.acceptable_set.clone(),
+        final_set: wordle.final_set.clone(),
+        opt: Opt {
+            difficult: wordle.opt.difficult,
+            seed: wordle.opt.seed,
+            strategy: wordle.opt.strategy,
+            ..Default::default()
+        },
+        patterns: wordle.patterns.clone(),
+        ..Default::default()
+    }
+}
+
+/// 未加修饰键的字符按键, 作为输入而不是命令
+fn is_typing(key: KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char(_))
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+}
+
 pub fn update(wordle: &mut Wordle, action: Action) -> Result<()> {
+    // 动画播放中按任意键跳过, 按键照常处理
+    if !matches!(
+        action,
+        Action::None
+            | Action::Init
+            | Action::Tick
+            | Action::Render
+            | Action::Resize(..)
+            | Action::FocusLost
+            | Action::FocusGained
+            | Action::HintReady(..)
+            | Action::AnalysisReady(..)
+    ) {
+        wordle.animation = None;
+    }
     match action {
-        Action::Init => {
-            if wordle.final_word.is_full() {
-                if wordle.is_final_word_valid() {
-                    wordle.ui_state = UiState::Main(MainState::Main);
-                } else {
-                    wordle.final_word = Word::default();
-                }
+        Action::Init if wordle.final_word.is_full() => {
+            if wordle.is_final_word_valid() {
+                enter_main(wordle)?;
+            } else {
+                wordle.final_word = Word::default();
             }
         }
         Action::Quit => {
@@ -72,15 +336,77 @@ pub fn update(wordle: &mut Wordle, action: Action) -> Result<()> {
         }
         Action::RemoveChar => match wordle.ui_state {
             UiState::Init => wordle.final_word.pop(),
-            UiState::Main(MainState::Main) => wordle.states.current_word.pop(),
+            UiState::Assist => {
+                wordle.states.message = None;
+                wordle.states.current_word.pop();
+            }
+            UiState::Main(MainState::Main) => {
+                wordle.states.completion = None;
+                wordle.remove_letter()?;
+            }
             _ => {}
         },
         // 用户按了enter键时更新操作
         Action::Enter => match wordle.ui_state {
+            UiState::Assist => {
+                if wordle.is_game_over() {
+                    // 开始新的一局
+                    wordle.clear();
+                    wordle.states.suggestions = wordle.suggestions(5);
+                } else if wordle.states.current_word.is_full() {
+                    let guess = wordle.states.current_word.to_string();
+                    let states = wordle
+                        .states
+                        .current_word
+                        .get_letters()
+                        .iter()
+                        .map(|l| l.1)
+                        .collect::<Vec<_>>();
+                    let pattern = Pattern::from_states(&states);
+                    // 出错时保留用户输入的单词和颜色
+                    let current_word = wordle.states.current_word.clone();
+                    match wordle.record_feedback(&guess, pattern) {
+                        Ok(_) => {
+                            wordle.states.message = None;
+                            wordle.states.suggestions = wordle.suggestions(5);
+                        }
+                        Err(e) => {
+                            wordle.states.current_word = current_word;
+                            wordle.states.message = Some(e.to_string());
+                        }
+                    }
+                }
+            }
+            UiState::Reverse => {
+                if wordle.is_game_over() {
+                    // 开始新的一局
+                    wordle.clear();
+                    next_reverse_guess(wordle)?;
+                } else if wordle.states.current_word.is_full() {
+                    let guess = wordle.states.current_word.to_string();
+                    let states = wordle
+                        .states
+                        .current_word
+                        .get_letters()
+                        .iter()
+                        .map(|l| l.1)
+                        .collect::<Vec<_>>();
+                    // 反馈矛盾时保留电脑的猜测和玩家标的颜色, 修改后可以重新提交
+                    let current_word = wordle.states.current_word.clone();
+                    match wordle.reverse_feedback(&guess, Pattern::from_states(&states)) {
+                        Ok(_) if wordle.is_game_over() => wordle.states.message = None,
+                        Ok(_) => next_reverse_guess(wordle)?,
+                        Err(e) => {
+                            wordle.states.current_word = current_word;
+                            wordle.states.message = Some(e.to_string());
+                        }
+                    }
+                }
+            }
             // 校验输入单词是否满足final word
             UiState::Init => {
                 if wordle.is_final_word_valid() {
-                    wordle.ui_state = UiState::Main(MainState::Main);
+                    enter_main(wordle)?;
                 }
             }
             UiState::Main(main_state) => match main_state {
@@ -88,21 +414,22 @@ pub fn update(wordle: &mut Wordle, action: Action) -> Result<()> {
                 MainState::Main => {
                     if wordle.states.current_word.is_full() {
                         if !wordle.is_game_over() {
-                            let check_result = wordle.check_word();
-                            wordle.states.current_checked_result = Some(check_result);
-                            match check_result {
-                                crate::wordle::CheckResult::Success => wordle.game_over = true,
-                                crate::wordle::CheckResult::Wrong => {
-                                    if wordle.states.current_try_times == MAX_RETRY_TIMES - 1 {
-                                        wordle.game_over = true;
-                                    } else {
-                                        wordle.states.next_state();
-                                    }
-                                }
-                                crate::wordle::CheckResult::Difficult => {
+                            let row = wordle.history_words.len();
+                            match wordle.submit_guess()? {
+                                CheckResult::Difficult => {
                                     wordle.ui_state = UiState::Main(MainState::Difficult);
+                                    animate(wordle, Animation::new(AnimationKind::Shake, row));
+                                }
+                                CheckResult::InValid => {
+                                    animate(wordle, Animation::new(AnimationKind::Shake, row));
                                 }
-                                crate::wordle::CheckResult::InValid => {}
+                                result => {
+                                    let won = result == CheckResult::Success;
+                                    animate(wordle, Animation::reveal(row, won));
+                                }
+                            }
+                            if wordle.is_game_over() {
+                                wordle.save_record()?;
                             }
                         } else {
                             wordle.ui_state = UiState::Main(MainState::GameOver);
@@ -115,28 +442,261 @@ pub fn update(wordle: &mut Wordle, action: Action) -> Result<()> {
                 MainState::GameOver => {
                     wordle.ui_state = UiState::Init;
                 }
+                MainState::Review => {
+                    wordle.ui_state = UiState::Main(MainState::GameOver);
+                }
+                // 第0项为开局, 其余为分支树中的节点
+                MainState::Branches => {
+                    let result = match wordle.states.selected.checked_sub(1) {
+                        None => wordle.rewind(0),
+                        Some(index) => match wordle.branch_tree().get(index) {
+                            Some(node) => wordle.switch_branch(&node.path.clone()),
+                            None => Ok(()),
+                        },
+                    };
+                    wordle.states.analysis = None;
+                    wordle.states.hint = None;
+                    wordle.states.hint_pending = false;
+                    match result {
+                        Ok(()) => wordle.states.message = None,
+                        Err(e) => wordle.states.message = Some(e.to_string()),
+                    }
+                    wordle.ui_state = UiState::Main(MainState::Main);
+                }
+                MainState::Help | MainState::Stats => {
+                    wordle.ui_state = UiState::Main(MainState::Main);
+                }
+                MainState::Settings => change_setting(wordle)?,
             },
         },
+        Action::CycleState(index) => {
+            wordle.states.message = None;
+            let letters = wordle.states.current_word.get_mut_letters();
+            if letters.len() == Word::MAX_LENGTH {
+                let letter = &mut letters[index];
+                letter.set_state(match letter.1 {
+                    LetterState::X | LetterState::G => LetterState::R,
+                    LetterState::R => LetterState::Y,
+                    LetterState::Y => LetterState::G,
+                });
+            }
+        }
         Action::InputChar(ch) => match wordle.ui_state {
+            UiState::Assist => {
+                wordle.states.message = None;
+                wordle.states.current_word.push(ch.to_ascii_lowercase());
+            }
             UiState::Init => {
                 wordle.final_word.push(ch);
             }
             UiState::Main(MainState::Main) => {
-                wordle.states.current_word.push(ch);
+                wordle.states.completion = None;
+                wordle.type_letter(ch)?;
             }
             _ => {}
         },
+        Action::NewGame => {
+            if let UiState::Main(MainState::Main | MainState::GameOver) = wordle.ui_state {
+                update(wordle, Action::ReNew)?;
+            }
+        }
         Action::ReNew => {
+            // 设置中修改的困难模式从新的一局开始生效
+            if let Some(hard) = wordle.pending_hard.take() {
+                wordle.opt.difficult = hard;
+            }
             wordle.reset()?;
             if wordle.final_word.is_empty() {
                 wordle.ui_state = UiState::Init;
             } else {
-                wordle.ui_state = UiState::Main(MainState::Main);
+                enter_main(wordle)?;
+            }
+        }
+        Action::Palette => {
+            if let UiState::Main(MainState::Main | MainState::GameOver) = wordle.ui_state {
+                wordle.palette = Some(Palette::default());
+            }
+        }
+        Action::PaletteInput(ch) => {
+            if let Some(ref mut palette) = wordle.palette {
+                palette.push(ch);
+            }
+        }
+        Action::PaletteDelete => {
+            if let Some(ref mut palette) = wordle.palette {
+                palette.pop();
+            }
+        }
+        Action::PaletteComplete => {
+            if let Some(ref mut palette) = wordle.palette {
+                palette.complete();
+            }
+        }
+        // 先关闭面板再执行, 失败时重新打开并显示错误
+        Action::PaletteRun => {
+            if let Some(palette) = wordle.palette.take() {
+                if let Err(e) = run_command(wordle, &palette.input) {
+                    wordle.palette = Some(Palette {
+                        error: Some(e.to_string()),
+                        ..palette
+                    });
+                }
+            }
+        }
+        Action::PaletteClose => wordle.palette = None,
+        Action::CompletionNext | Action::CompletionPrev => {
+            let count = completions(wordle).len();
+            if count > 0 {
+                let next = matches!(action, Action::CompletionNext);
+                wordle.states.completion = Some(match wordle.states.completion {
+                    None if next => 0,
+                    None => count - 1,
+                    Some(index) if next => (index + 1) % count,
+                    Some(index) => (index + count - 1) % count,
+                });
+            }
+        }
+        Action::CompletionAccept => {
+            let word = wordle
+                .states
+                .completion
+                .and_then(|index| completions(wordle).get(index).map(|word| word.to_string()));
+            wordle.states.completion = None;
+            if let Some(word) = word {
+                wordle.type_word(&word)?;
+            }
+        }
+        Action::CompletionCancel => wordle.states.completion = None,
+        Action::Branches => {
+            if let UiState::Main(MainState::Main | MainState::GameOver) = wordle.ui_state {
+                if wordle.opt.practice {
+                    // 默认选中当前分支的最后一个节点
+                    let tree = wordle.branch_tree();
+                    wordle.states.selected = tree
+                        .iter()
+                        .rposition(|node| node.active && node.depth() == wordle.history_words.len())
+                        .map_or(0, |index| index + 1);
+                    wordle.ui_state = UiState::Main(MainState::Branches);
+                } else {
+                    wordle.states.message = Some(t!("ui.branches.practice").to_string());
+                }
+            }
+        }
+        Action::SwitchTheme => {
+            wordle.theme = wordle.theme.next();
+            wordle.settings.theme = wordle.theme.name.clone();
+            wordle.states.message = Some(t!("ui.theme", wordle.theme.name));
+            save_settings(wordle);
+        }
+        Action::Help | Action::Stats | Action::Settings => {
+            if let UiState::Main(MainState::Main | MainState::GameOver) = wordle.ui_state {
+                wordle.states.selected = 0;
+                wordle.ui_state = UiState::Main(match action {
+                    Action::Help => MainState::Help,
+                    Action::Stats => MainState::Stats,
+                    _ => MainState::Settings,
+                });
+            }
+        }
+        Action::ChangeSetting => change_setting(wordle)?,
+        Action::SelectUp => {
+            wordle.states.selected = wordle.states.selected.saturating_sub(1);
+        }
+        Action::SelectDown => {
+            let last = match wordle.ui_state {
+                UiState::Main(MainState::Settings) => SETTINGS_ROWS - 1,
+                _ => wordle.branch_tree().len(),
+            };
+            if wordle.states.selected < last {
+                wordle.states.selected += 1;
+            }
+        }
+        Action::Undo => {
+            if let UiState::Main(MainState::Main | MainState::GameOver) = wordle.ui_state {
+                match wordle.undo() {
+                    Ok(()) => {
+                        wordle.states.analysis = None;
+                        wordle.ui_state = UiState::Main(MainState::Main);
+                    }
+                    Err(e) => wordle.states.message = Some(e.to_string()),
+                }
+            }
+        }
+        // 求解可能要几秒, 在后台计算, 同一组历史猜测只计算一次
+        Action::Hint => {
+            if let UiState::Main(MainState::Main) = wordle.ui_state {
+                let states = &wordle.states;
+                if !wordle.is_game_over() && states.hint.is_none() && !states.hint_pending {
+                    wordle.states.hint_pending = true;
+                    let solver = solver_snapshot(wordle);
+                    run_blocking(wordle, move || {
+                        let hint = solver.hint();
+                        Action::HintReady(solver.history_words, hint)
+                    })?;
+                }
+            }
+        }
+        Action::HintReady(history, hint) => {
+            wordle.states.hint_pending = false;
+            if history == wordle.history_words {
+                wordle.states.hint = hint;
             }
         }
         Action::PopUp => {
             wordle.ui_state = UiState::Main(MainState::GameOver);
         }
+        // 复盘界面先打开, 分析在后台计算
+        Action::Review => {
+            wordle.ui_state = UiState::Main(MainState::Review);
+            if wordle.states.analysis.is_none() && !wordle.states.analysis_pending {
+                wordle.states.analysis_pending = true;
+                let solver = solver_snapshot(wordle);
+                run_blocking(wordle, move || {
+                    let analysis = solver.analyze();
+                    Action::AnalysisReady(solver.history_words, analysis)
+                })?;
+            }
+        }
+        Action::AnalysisReady(history, analysis) => {
+            wordle.states.analysis_pending = false;
+            if history == wordle.history_words {
+                wordle.states.analysis = Some(analysis);
+            }
+        }
+        Action::Render => {
+            wordle.animation = wordle.animation.and_then(Animation::advance);
+        }
+        Action::FocusLost | Action::Suspend => wordle.clock.pause(),
+        Action::FocusGained => wordle.clock.resume(),
+        // 回放时每个tick输入一个字母, 输入完整后提交, 等待动画播放完毕
+        Action::Tick => {
+            let playing = matches!(
+                wordle.ui_state,
+                UiState::Main(MainState::Main | MainState::Difficult)
+            );
+            wordle.clock.update(playing && !wordle.is_game_over());
+            if let (UiState::Main(MainState::Main), Some(guess), None) = (
+                wordle.ui_state,
+                wordle.replay_queue.front().cloned(),
+                wordle.animation,
+            ) {
+                let typed = wordle.states.current_word.get_letters().len();
+                match guess.chars().nth(typed) {
+                    Some(ch) => wordle.type_letter(ch)?,
+                    None => {
+                        wordle.replay_queue.pop_front();
+                        update(wordle, Action::Enter)?;
+                    }
+                }
+            }
+        }
+        Action::Share => {
+            // 终端支持OSC 52时会写入系统剪贴板. 与界面一样写到stderr, stdout可能被重定向
+            let mut stderr = io::stderr();
+            stderr.write_all(osc52(&wordle.share_text()).as_bytes())?;
+            stderr.flush()?;
+            wordle.states.message = Some(t!("ui.shared").to_string());
+        }
         Action::EnterMain => {
             wordle.ui_state = UiState::Main(MainState::Main);
         }
@@ -144,3 +704,159 @@ pub fn update(wordle: &mut Wordle, action: Action) -> Result<()> {
     }
     Ok(())
 }
+
+/// 开始播放猜测行动画, 在设置中或用`--no-animation`关闭动画时不播放
+fn animate(wordle: &mut Wordle, animation: Animation) {
+    if animations_enabled(wordle) {
+        wordle.animation = Some(animation);
+    }
+}
+
+/// 设置界面的行数: 困难模式, 主题, 单词长度, 动画, 自动补全
+pub const SETTINGS_ROWS: usize = 5;
+
+///
+/// 切换设置界面中选中的设置项, 并保存设置
+///
+fn change_setting(wordle: &mut Wordle) -> Result<()> {
+    wordle.states.message = None;
+    match wordle.states.selected {
+        0 => set_hard(wordle, !next_game_hard(wordle))?,
+        1 => {
+            wordle.theme = wordle.theme.next();
+            wordle.settings.theme = wordle.theme.name.clone();
+        }
+        2 => wordle.settings.next_word_length(),
+        3 => {
+            // 在设置中修改后不再使用命令行参数
+            wordle.settings.animations = !animations_enabled(wordle);
+            wordle.opt.no_animation = false;
+            wordle.animation = None;
+        }
+        _ => wordle.settings.autocomplete = wordle.settings.autocomplete.next(),
+    }
+    save_settings(wordle);
+    Ok(())
+}
+
+///
+/// 修改困难模式设置, 还没有猜测时立即重新开始本局, 否则从下一局开始生效
+///
+fn set_hard(wordle: &mut Wordle, hard: bool) -> Result<()> {
+    wordle.settings.hard = hard;
+    if wordle.history_words.is_empty() && !wordle.is_game_over() {
+        wordle.pending_hard = None;
+        wordle.opt.difficult = hard;
+        wordle.start_game()?;
+    } else {
+        wordle.pending_hard = (hard != wordle.opt.difficult).then_some(hard);
+        wordle.states.message = Some(t!("ui.settings.next_game").to_string());
+    }
+    Ok(())
+}
+
+///
+/// 下一局是否为困难模式, 包括命令行参数`--difficult`和设置中尚未生效的修改
+///
+pub fn next_game_hard(wordle: &Wordle) -> bool {
+    wordle.pending_hard.unwrap_or(wordle.opt.difficult)
+}
+
+///
+/// 是否播放动画, `--no-animation`优先于保存的设置
+///
+pub fn animations_enabled(wordle: &Wordle) -> bool {
+    wordle.settings.animations && !wordle.opt.no_animation
+}
+
+/// 保存设置, 失败时在状态栏显示错误
+fn save_settings(wordle: &mut Wordle) {
+    if let Some(ref path) = wordle.opt.settings {
+        if let Err(e) = wordle.settings.save(path) {
+            wordle.states.message = Some(e.to_string());
+        }
+    }
+}
+
+///
+/// 执行命令面板中输入的命令, 空输入只关闭面板
+///
+fn run_command(wordle: &mut Wordle, input: &str) -> Result<()> {
+    if input.trim().is_empty() {
+        return Ok(());
+    }
+    let action = match input.parse()? {
+        PaletteCommand::New => Action::NewGame,
+        PaletteCommand::Hint => Action::Hint,
+        PaletteCommand::Stats => Action::Stats,
+        PaletteCommand::Share => Action::Share,
+        PaletteCommand::Quit => Action::Quit,
+        PaletteCommand::Help => Action::Help,
+        PaletteCommand::Settings => Action::Settings,
+        PaletteCommand::Theme => Action::SwitchTheme,
+        PaletteCommand::Undo => Action::Undo,
+        PaletteCommand::Seed(seed) => {
+            wordle.opt.seed = Some(seed);
+            wordle.opt.random = true;
+            wordle.opt.word = None;
+            Action::ReNew
+        }
+        PaletteCommand::Hard(hard) => {
+            wordle.states.message = None;
+            set_hard(wordle, hard)?;
+            save_settings(wordle);
+            return Ok(());
+        }
+        PaletteCommand::Load(path) => return load_record(wordle, &path),
+    };
+    update(wordle, action)
+}
+
+///
+/// 加载对局记录并回放, 与`--replay`相同
+///
+fn load_record(wordle: &mut Wordle, path: &Path) -> Result<()> {
+    let record = GameRecord::load(path)?;
+    record.verify()?;
+    if !wordle.final_set.contains(&record.answer) {
+        return Err(WordError::CustomError(t!(
+            "palette.not_final",
+            record.answer
+        )));
+    }
+    wordle.opt.word = Some(record.answer.clone());
+    wordle.opt.random = false;
+    wordle.opt.difficult = record.hard;
+    wordle.reset()?;
+    enter_main(wordle)?;
+    wordle.replay_queue = record.guesses.into_iter().map(|g| g.word).collect();
+    Ok(())
+}
+
+///
+/// 进入游戏界面, 恢复的一局继续进行, 否则开始新的一局
+///
+fn enter_main(wordle: &mut Wordle) -> Result<()> {
+    if !wordle.journal.in_progress() {
+        wordle.start_game()?;
+        wordle.clock = Clock::default();
+    }
+    wordle.ui_state = UiState::Main(MainState::Main);
+    Ok(())
+}
+
+///
+/// 反向模式下将电脑的下一个猜测放入当前行
+///
+pub fn next_reverse_guess(wordle: &mut Wordle) -> Result<()> {
+    wordle.states.message = None;
+    match wordle.reverse_guess() {
+        Some(guess) => wordle.states.current_word = Word::parse(guess)?,
+        // 玩家想的词不在答案词库中, 结束本局, 回车开始新的一局
+        None => {
+            wordle.states.message = Some(t!("cmd.reverse.no_fit").to_string());
+            wordle.game_over();
+        }
+    }
+    Ok(())
+}
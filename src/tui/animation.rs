@@ -0,0 +1,140 @@
+//! 猜测行的动画
+//!
+//! 由`Event::Render`驱动, 每帧前进一步: 提交后逐个翻开方块, 无效或困难模式不满足时整行抖动,
+//! 猜中后方块依次跳起. 动画期间按任意键跳过, 也可以在设置中或用`--no-animation`关闭.
+use ratatui::layout::Rect;
+
+// 每个方块翻开的帧数, 相邻方块依次错开
+const FLIP_FRAMES: u32 = 12;
+// 抖动时每一帧的水平偏移
+const SHAKE_OFFSETS: [u16; 16] = [1, 2, 1, 0, 1, 2, 1, 0, 1, 2, 1, 0, 1, 1, 0, 0];
+// 每个方块跳起的帧数, 相邻方块依次错开
+const BOUNCE_FRAMES: u32 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationKind {
+    // 逐个翻开方块显示反馈
+    Reveal,
+    // 整行左右抖动
+    Shake,
+    // 猜中后方块依次跳起
+    Bounce,
+}
+
+/// 方块在当前帧的显示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileFrame {
+    // 正常显示
+    Normal,
+    // 尚未翻开, 按未知状态显示
+    Hidden,
+    // 翻转中, 方块收窄, `true`表示已经翻到反馈一面
+    Flipping(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Animation {
+    pub kind: AnimationKind,
+    // 播放动画的猜测行
+    pub row: usize,
+    pub frame: u32,
+    // 翻开完成后是否接着播放跳起动画
+    pub won: bool,
+}
+
+impl Animation {
+    pub fn new(kind: AnimationKind, row: usize) -> Self {
+        Animation {
+            kind,
+            row,
+            frame: 0,
+            won: false,
+        }
+    }
+
+    /// 翻开一行猜测, `won`为真时翻开后接着跳起
+    pub fn reveal(row: usize, won: bool) -> Self {
+        Animation {
+            won,
+            ..Animation::new(AnimationKind::Reveal, row)
+        }
+    }
+
+    /// 动画的总帧数
+    pub fn frames(&self) -> u32 {
+        match self.kind {
+            AnimationKind::Reveal => FLIP_FRAMES * 5,
+            AnimationKind::Shake => SHAKE_OFFSETS.len() as u32,
+            AnimationKind::Bounce => BOUNCE_FRAMES * 6,
+        }
+    }
+
+    ///
+    /// 前进一帧, 返回下一帧的动画, 播放完毕时返回`None`
+    ///
+    pub fn advance(self) -> Option<Self> {
+        let frame = self.frame + 1;
+        if frame < self.frames() {
+            return Some(Animation { frame, ..self });
+        }
+        match self.kind {
+            AnimationKind::Reveal if self.won => {
+                Some(Animation::new(AnimationKind::Bounce, self.row))
+            }
+            _ => None,
+        }
+    }
+
+    /// 第`index`个方块在当前帧的显示方式
+    pub fn tile(&self, index: usize) -> TileFrame {
+        if self.kind != AnimationKind::Reveal {
+            return TileFrame::Normal;
+        }
+        let start = index as u32 * FLIP_FRAMES;
+        if self.frame < start {
+            TileFrame::Hidden
+        } else if self.frame < start + FLIP_FRAMES {
+            TileFrame::Flipping(self.frame >= start + FLIP_FRAMES / 2)
+        } else {
+            TileFrame::Normal
+        }
+    }
+
+    /// 整行的水平偏移
+    pub fn offset(&self) -> u16 {
+        match self.kind {
+            AnimationKind::Shake => SHAKE_OFFSETS[self.frame as usize % SHAKE_OFFSETS.len()],
+            _ => 0,
+        }
+    }
+
+    /// 第`index`个方块是否处于跳起状态
+    pub fn lifted(&self, index: usize) -> bool {
+        let start = index as u32 * BOUNCE_FRAMES;
+        self.kind == AnimationKind::Bounce
+            && (start..start + BOUNCE_FRAMES * 2).contains(&self.frame)
+    }
+
+    ///
+    /// 按当前帧调整方块所在的区域: 抖动时右移, 翻转时收窄, 跳起时移到相邻的空行
+    ///
+    pub fn tile_area(&self, index: usize, area: Rect) -> Rect {
+        let mut area = Rect {
+            x: area.x + self.offset(),
+            ..area
+        };
+        if let TileFrame::Flipping(_) = self.tile(index) {
+            area.x += area.width / 2;
+            area.width = 1;
+        }
+        if self.lifted(index) {
+            // 第一行上方是边框, 改为落到下方的空行
+            if self.row == 0 {
+                area.y += 1;
+            } else {
+                area.y = area.y.saturating_sub(1);
+            }
+        }
+        area
+    }
+}
@@ -0,0 +1,43 @@
+//! 对局计时
+//!
+//! 每个`Event::Tick`累加一次用时, 终端失去焦点或挂起时暂停, 对局结束后停止.
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Clock {
+    elapsed: Duration,
+    // 上一次累加的时间, 为空时下一次只记录起点
+    last: Option<Instant>,
+    pub paused: bool,
+}
+
+impl Clock {
+    /// 按当前时间累加用时, `running`为假时(如对局已结束)只更新起点
+    pub fn update(&mut self, running: bool) {
+        let now = Instant::now();
+        if let (Some(last), true, false) = (self.last, running, self.paused) {
+            self.elapsed += now - last;
+        }
+        self.last = Some(now);
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// 恢复计时, 暂停期间的时间不计入
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.last = None;
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// 显示为`mm:ss`
+    pub fn display(&self) -> String {
+        let secs = self.elapsed.as_secs();
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
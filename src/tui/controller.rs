@@ -6,11 +6,18 @@ use std::{
 };
 
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture, Event as CrosstermEvent, KeyEventKind},
+    cursor,
+    event::{
+        DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+        Event as CrosstermEvent, KeyEventKind, MouseButton, MouseEventKind,
+    },
     terminal::{self, is_raw_mode_enabled, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use futures::{FutureExt, StreamExt};
-use ratatui::{prelude::CrosstermBackend, Terminal};
+use futures::{stream, FutureExt, Stream, StreamExt};
+use ratatui::{
+    prelude::{Backend, CrosstermBackend},
+    Terminal,
+};
 use tokio::{
     sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     task::JoinHandle,
@@ -25,8 +32,8 @@ use crate::{
 use super::{
     action::{get_action, update, Action},
     event::Event,
-    ui::{self},
-    widgets::init_keyboard,
+    ui::{self, UiState},
+    widgets::layout_keyboard,
 };
 
 pub type CrosstermTerminal = Terminal<CrosstermBackend<io::Stderr>>;
@@ -77,9 +84,24 @@ impl Tui {
 
     pub fn resume(&mut self) -> Result<()> {
         self.enter()?;
+        // 后台期间屏幕可能被其他程序改写, 清屏后完整重绘
+        self.terminal.clear()?;
         Ok(())
     }
 
+    ///
+    /// 恢复终端后挂起进程(同shell中的Ctrl-Z), 回到前台后重新进入tui
+    ///
+    pub fn suspend(&mut self) -> Result<()> {
+        self.exit()?;
+        #[cfg(unix)]
+        // SAFETY: 只是向当前进程发送信号
+        unsafe {
+            libc::raise(libc::SIGTSTP);
+        }
+        self.resume()
+    }
+
     pub fn start(&mut self) {
         let tick_delay = Duration::from_secs_f64(1.0 / self.tick_rate);
         let render_delay = Duration::from_secs_f64(1.0 / self.frame_rate);
@@ -105,14 +127,21 @@ impl Tui {
                         match maybe_event {
                             Some(Ok(evt)) => {
                                 match evt {
-                                    CrosstermEvent::Key(key) => {
-                                    if key.kind == KeyEventKind::Press {
+                                    CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => {
                                         _event_tx.send(Event::Key(key)).unwrap();
-                                    }
                                     },
                                     CrosstermEvent::Mouse(mouse) => {
                                         _event_tx.send(Event::Mouse(mouse)).unwrap();
                                     },
+                                    CrosstermEvent::Resize(width, height) => {
+                                        _event_tx.send(Event::Resize(width, height)).unwrap();
+                                    },
+                                    CrosstermEvent::FocusLost => {
+                                        _event_tx.send(Event::FocusLost).unwrap();
+                                    },
+                                    CrosstermEvent::FocusGained => {
+                                        _event_tx.send(Event::FocusGained).unwrap();
+                                    },
                                     _ => {}
                                 }
                             },
@@ -135,7 +164,12 @@ impl Tui {
 
     pub fn enter(&mut self) -> Result<()> {
         terminal::enable_raw_mode()?;
-        crossterm::execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+        crossterm::execute!(
+            io::stderr(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange
+        )?;
 
         self.start();
         Ok(())
@@ -163,10 +197,8 @@ impl Tui {
         self.stop()?;
         if is_raw_mode_enabled()? {
             self.flush()?;
-            crossterm::execute!(io::stderr(), DisableMouseCapture, LeaveAlternateScreen,)?;
-            crossterm::terminal::disable_raw_mode()?;
         }
-        self.terminal.show_cursor()?;
+        restore()?;
         Ok(())
     }
 
@@ -196,49 +228,151 @@ impl DerefMut for Tui {
 
 impl Drop for Tui {
     fn drop(&mut self) {
-        self.exit().unwrap();
+        if let Err(e) = self.exit() {
+            eprintln!("failed to restore the terminal: {}", e);
+        }
+    }
+}
+
+///
+/// 恢复终端: 退出raw模式和备用屏幕, 显示光标. 终端已恢复时什么也不做
+///
+pub fn restore() -> Result<()> {
+    if is_raw_mode_enabled()? {
+        crossterm::execute!(
+            io::stderr(),
+            DisableMouseCapture,
+            DisableFocusChange,
+            LeaveAlternateScreen,
+            cursor::Show
+        )?;
+        terminal::disable_raw_mode()?;
     }
+    Ok(())
+}
+
+///
+/// 安装`color_eyre`的错误报告和panic hook. panic时先恢复终端再打印报告,
+/// 避免报告被备用屏幕吞掉, 终端停留在raw模式
+///
+pub fn install_hooks() -> Result<()> {
+    let (panic_hook, eyre_hook) = color_eyre::config::HookBuilder::default().into_hooks();
+    eyre_hook
+        .install()
+        .map_err(|e| WordError::EyreError(e.into()))?;
+    let panic_hook = panic_hook.into_panic_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Err(e) = restore() {
+            eprintln!("failed to restore the terminal: {}", e);
+        }
+        panic_hook(info);
+    }));
+    Ok(())
+}
+
+/// 主循环结束的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stopped {
+    Quit,
+    // 按了Ctrl-Z, 需要挂起到后台
+    Suspend,
+    // 事件流结束
+    EventsEnded,
 }
 
-/// 运行游戏
+/// 运行游戏, 出错时也会先恢复终端再返回错误
 pub async fn run(wordle: &mut Wordle) -> Result<()> {
-    let (action_tx, mut action_rx) = unbounded_channel();
     let mut tui = Tui::new()?;
     tui.enter()?;
-    let keyboards = init_keyboard();
+    let result = loop {
+        let Tui {
+            terminal, event_rx, ..
+        } = &mut tui;
+        let events = stream::poll_fn(|cx| event_rx.poll_recv(cx));
+        match drive(wordle, terminal, events).await {
+            Ok(Stopped::Suspend) => {
+                if let Err(e) = tui.suspend() {
+                    break Err(e);
+                }
+                wordle.clock.resume();
+            }
+            result => break result.map(|_| ()),
+        }
+    };
+    tui.exit()?;
 
-    loop {
-        let event = tui.next().await?;
+    result
+}
+
+///
+/// 游戏主循环: 从`events`读取事件并更新状态, 收到`Event::Render`时绘制到`terminal`.
+/// 不依赖真实终端, 测试中可以用`TestBackend`和脚本化的事件驱动. 事件流结束, 退出游戏
+/// 或需要挂起时返回
+///
+pub async fn drive<B, S>(
+    wordle: &mut Wordle,
+    terminal: &mut Terminal<B>,
+    mut events: S,
+) -> Result<Stopped>
+where
+    B: Backend,
+    S: Stream<Item = Event> + Unpin,
+{
+    let (action_tx, mut action_rx) = unbounded_channel();
+    let keyboards = layout_keyboard(&wordle.layout);
+    let mut suspend = false;
 
+    while let Some(event) = events.next().await {
         match event {
-            Event::Init => action_tx.send(Action::Init).unwrap(),
+            // 挂起后恢复时也会收到Init, 只有在输入答案的界面才需要处理
+            Event::Init if wordle.ui_state == UiState::Init => {
+                action_tx.send(Action::Init).unwrap()
+            }
+            Event::Init => {}
             Event::Quit => action_tx.send(Action::Quit).unwrap(),
             Event::Error => action_tx.send(Action::Error).unwrap(),
             Event::Tick => action_tx.send(Action::Tick).unwrap(),
             Event::Render => action_tx.send(Action::Render).unwrap(),
-            Event::Key(_) => {
+            Event::Key(_) | Event::Resize(..) | Event::FocusLost | Event::FocusGained => {
                 let action = get_action(wordle, event);
                 action_tx.send(action).unwrap();
             }
+            // 点击屏幕键盘或弹窗按钮, 按当前终端大小做命中测试
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                let area = terminal.size()?;
+                if let Some(key) = ui::hit_test(wordle, area, &keyboards, mouse.column, mouse.row) {
+                    let action = get_action(wordle, Event::Key(key));
+                    action_tx.send(action).unwrap();
+                }
+            }
             Event::Mouse(_) => {}
         }
+        // 后台计算完成的结果随下一个事件处理
+        while let Some(action) = wordle.jobs.try_recv() {
+            action_tx.send(action).unwrap();
+        }
 
         while let Ok(action) = action_rx.try_recv() {
             update(wordle, action.clone())?;
 
-            if let Action::Render = action {
-                tui.draw(|f| {
-                    ui::ui(wordle, f, &keyboards);
-                })?;
+            match action {
+                Action::Render | Action::Resize(..) => {
+                    terminal.draw(|f| {
+                        ui::ui(wordle, f, &keyboards);
+                    })?;
+                }
+                Action::Suspend => suspend = true,
+                _ => {}
             }
         }
 
         if wordle.exit {
-            break;
+            return Ok(Stopped::Quit);
+        }
+        if suspend {
+            return Ok(Stopped::Suspend);
         }
     }
 
-    tui.exit()?;
-
-    Ok(())
+    Ok(Stopped::EventsEnded)
 }
@@ -11,4 +11,8 @@ pub enum Event {
     Render,
     Key(KeyEvent),
     Mouse(MouseEvent),
+    // 终端大小变化后的列数和行数
+    Resize(u16, u16),
+    FocusGained,
+    FocusLost,
 }
@@ -0,0 +1,466 @@
+//! 可配置的按键绑定
+//!
+//! 按键组合(如`ctrl-z`, `f1`, `?`)绑定到命令, 从json文件加载, 例如
+//! `{"preset": "vim", "bindings": {"share": ["ctrl-s"], "new-game": ["ctrl-n", "f5"]}}`.
+//! 文件中给出的命令替换预设中该命令的全部按键, 空列表表示取消绑定.
+//! 加载时检查冲突: 同一按键在同一界面绑定了多个命令, 或未加修饰键的字母挡住了输入.
+use std::{collections::BTreeMap, fmt, fs, path::Path, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::{
+    error::{Result, WordError},
+    t,
+};
+
+use super::action::Action;
+
+/// 内置预设
+pub const PRESETS: [&str; 2] = ["default", "vim"];
+
+/// 可以绑定按键的命令
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+    // 提交猜测, 在弹窗中确认
+    Submit,
+    Delete,
+    Quit,
+    Hint,
+    Share,
+    Stats,
+    Help,
+    Settings,
+    NewGame,
+    Undo,
+    Branches,
+    Theme,
+    Suspend,
+    // 打开命令面板
+    Palette,
+    // 补全命令面板和自动补全弹窗中的输入
+    Complete,
+    // 结束弹窗中打开复盘界面
+    Review,
+    // 分支树和设置界面中选择上一项/下一项
+    SelectUp,
+    SelectDown,
+}
+
+/// 命令生效的界面
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    // 所有界面
+    Global,
+    // 输入答案和猜测的界面
+    Main,
+    // 分支树和设置等列表界面
+    List,
+    // 结束弹窗, 没有输入, 可以绑定字母. 弹窗中输入界面的命令也生效
+    Popup,
+}
+
+impl Scope {
+    /// 两个界面的命令是否会同时生效
+    fn overlaps(&self, other: Scope) -> bool {
+        match (self, other) {
+            (Scope::Global, _) | (_, Scope::Global) => true,
+            (Scope::Main, Scope::Popup) | (Scope::Popup, Scope::Main) => true,
+            (scope, other) => *scope == other,
+        }
+    }
+}
+
+impl Command {
+    pub const ALL: [Command; 18] = [
+        Command::Submit,
+        Command::Delete,
+        Command::Quit,
+        Command::Hint,
+        Command::Share,
+        Command::Stats,
+        Command::Help,
+        Command::Settings,
+        Command::NewGame,
+        Command::Undo,
+        Command::Branches,
+        Command::Theme,
+        Command::Suspend,
+        Command::Palette,
+        Command::Complete,
+        Command::Review,
+        Command::SelectUp,
+        Command::SelectDown,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Submit => "submit",
+            Command::Delete => "delete",
+            Command::Quit => "quit",
+            Command::Hint => "hint",
+            Command::Share => "share",
+            Command::Stats => "stats",
+            Command::Help => "help",
+            Command::Settings => "settings",
+            Command::NewGame => "new-game",
+            Command::Undo => "undo",
+            Command::Branches => "branches",
+            Command::Theme => "theme",
+            Command::Suspend => "suspend",
+            Command::Palette => "palette",
+            Command::Complete => "complete",
+            Command::Review => "review",
+            Command::SelectUp => "select-up",
+            Command::SelectDown => "select-down",
+        }
+    }
+
+    /// 帮助界面中显示的说明
+    pub fn label(&self) -> &'static str {
+        match self {
+            Command::Submit => t!("keymap.submit"),
+            Command::Delete => t!("keymap.delete"),
+            Command::Quit => t!("keymap.quit"),
+            Command::Hint => t!("keymap.hint"),
+            Command::Share => t!("keymap.share"),
+            Command::Stats => t!("keymap.stats"),
+            Command::Help => t!("keymap.help"),
+            Command::Settings => t!("keymap.settings"),
+            Command::NewGame => t!("keymap.new_game"),
+            Command::Undo => t!("keymap.undo"),
+            Command::Branches => t!("keymap.branches"),
+            Command::Theme => t!("keymap.theme"),
+            Command::Suspend => t!("keymap.suspend"),
+            Command::Palette => t!("keymap.palette"),
+            Command::Complete => t!("keymap.complete"),
+            Command::Review => t!("keymap.review"),
+            Command::SelectUp => t!("keymap.select_up"),
+            Command::SelectDown => t!("keymap.select_down"),
+        }
+    }
+
+    pub fn scope(&self) -> Scope {
+        match self {
+            Command::Theme | Command::Suspend => Scope::Global,
+            Command::SelectUp | Command::SelectDown => Scope::List,
+            Command::Review => Scope::Popup,
+            _ => Scope::Main,
+        }
+    }
+
+    ///
+    /// 命令对应的操作, 提交的含义取决于当前界面, 由调用方处理
+    ///
+    pub fn action(&self) -> Action {
+        match self {
+            Command::Submit => Action::Enter,
+            Command::Delete => Action::RemoveChar,
+            Command::Quit => Action::Quit,
+            Command::Hint => Action::Hint,
+            Command::Share => Action::Share,
+            Command::Stats => Action::Stats,
+            Command::Help => Action::Help,
+            Command::Settings => Action::Settings,
+            Command::NewGame => Action::NewGame,
+            Command::Undo => Action::Undo,
+            Command::Branches => Action::Branches,
+            Command::Theme => Action::SwitchTheme,
+            Command::Suspend => Action::Suspend,
+            Command::Palette => Action::Palette,
+            Command::Complete => Action::CompletionNext,
+            Command::Review => Action::Review,
+            Command::SelectUp => Action::SelectUp,
+            Command::SelectDown => Action::SelectDown,
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Command::ALL
+            .into_iter()
+            .find(|command| command.name() == s)
+            .ok_or_else(|| WordError::CustomError(t!("keymap.unknown_command", s)))
+    }
+}
+
+/// 按键组合, 字符键忽略shift(大小写和符号已经体现在字符中)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Chord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Chord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(ch) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                // 终端中ctrl/alt组合收到的总是小写字母
+                if modifiers.is_empty() {
+                    KeyCode::Char(ch)
+                } else {
+                    KeyCode::Char(ch.to_ascii_lowercase())
+                }
+            }
+            code => code,
+        };
+        Chord { code, modifiers }
+    }
+
+    /// 未加修饰键的字母, 绑定到输入界面的命令会挡住输入
+    pub fn is_letter(&self) -> bool {
+        matches!(self.code, KeyCode::Char(ch) if ch.is_alphabetic())
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl From<KeyEvent> for Chord {
+    fn from(key: KeyEvent) -> Self {
+        Chord::new(key.code, key.modifiers)
+    }
+}
+
+impl FromStr for Chord {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || WordError::CustomError(t!("keymap.invalid_key", s));
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let (modifier, len) = if lower.starts_with("ctrl-") {
+                (KeyModifiers::CONTROL, 5)
+            } else if lower.starts_with("alt-") {
+                (KeyModifiers::ALT, 4)
+            } else if lower.starts_with("shift-") {
+                (KeyModifiers::SHIFT, 6)
+            } else {
+                break;
+            };
+            modifiers |= modifier;
+            rest = &rest[len..];
+        }
+        // `ctrl--`去掉修饰键后剩下按键`-`
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) => KeyCode::Char(ch),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "tab" => KeyCode::Tab,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                name => match name.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+        Ok(Chord::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl-"),
+            (KeyModifiers::ALT, "alt-"),
+            (KeyModifiers::SHIFT, "shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(ch) => write!(f, "{}", ch),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// 按键绑定文件的内容
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct KeymapFile {
+    preset: Option<String>,
+    bindings: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    pub preset: String,
+    pub bindings: Vec<(Chord, Command)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::preset("default").expect("built-in keymap preset")
+    }
+}
+
+impl Keymap {
+    ///
+    /// 内置预设: default 与之前的固定按键一致, vim 另外支持`ctrl-h`删除, `ctrl-j`提交,
+    /// 列表界面中`j`/`k`和`ctrl-n`/`ctrl-p`选择
+    ///
+    pub fn preset(name: &str) -> Option<Self> {
+        let mut bindings = vec![
+            ("enter", Command::Submit),
+            ("backspace", Command::Delete),
+            ("esc", Command::Quit),
+            ("?", Command::Hint),
+            ("ctrl-s", Command::Share),
+            ("f1", Command::Help),
+            ("f2", Command::Stats),
+            ("f3", Command::Settings),
+            ("ctrl-n", Command::NewGame),
+            ("ctrl-u", Command::Undo),
+            ("ctrl-b", Command::Branches),
+            ("ctrl-t", Command::Theme),
+            ("ctrl-z", Command::Suspend),
+            (":", Command::Palette),
+            ("tab", Command::Complete),
+            ("a", Command::Review),
+            ("up", Command::SelectUp),
+            ("down", Command::SelectDown),
+        ];
+        match name {
+            "default" => {}
+            "vim" => bindings.extend([
+                ("ctrl-h", Command::Delete),
+                ("ctrl-j", Command::Submit),
+                ("k", Command::SelectUp),
+                ("ctrl-p", Command::SelectUp),
+                ("j", Command::SelectDown),
+                ("ctrl-n", Command::SelectDown),
+            ]),
+            _ => return None,
+        }
+        let bindings = bindings
+            .into_iter()
+            .map(|(chord, command)| Ok((chord.parse()?, command)))
+            .collect::<Result<_>>()
+            .ok()?;
+        Some(Keymap {
+            preset: name.to_string(),
+            bindings,
+        })
+    }
+
+    ///
+    /// 从json文件加载按键绑定, 文件不存在时使用默认预设. 有冲突时返回全部冲突
+    ///
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Keymap::default()),
+            Err(e) => return Err(e.into()),
+        };
+        let file: KeymapFile = serde_json::from_str(&content)?;
+        let preset = file.preset.as_deref().unwrap_or("default");
+        let mut keymap = Keymap::preset(preset)
+            .ok_or_else(|| WordError::CustomError(t!("keymap.unknown_preset", preset)))?;
+        for (name, chords) in file.bindings {
+            let command = name.parse::<Command>()?;
+            keymap.bindings.retain(|(_, bound)| *bound != command);
+            for chord in chords {
+                keymap.bindings.push((chord.parse()?, command));
+            }
+        }
+        let conflicts = keymap.conflicts();
+        if !conflicts.is_empty() {
+            return Err(WordError::CustomError(conflicts.join("; ")));
+        }
+        Ok(keymap)
+    }
+
+    ///
+    /// 检查冲突的绑定: 作用界面重叠的两个命令使用了同一按键, 或字母挡住了输入
+    ///
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = vec![];
+        for (index, (chord, command)) in self.bindings.iter().enumerate() {
+            if chord.is_letter() && matches!(command.scope(), Scope::Main | Scope::Global) {
+                conflicts.push(t!("keymap.shadows_typing", chord, command.name()));
+            }
+            for (other_chord, other) in &self.bindings[index + 1..] {
+                let overlaps = command.scope().overlaps(other.scope());
+                if chord == other_chord && command != other && overlaps {
+                    conflicts.push(t!("keymap.conflict", chord, command.name(), other.name()));
+                }
+            }
+        }
+        conflicts
+    }
+
+    ///
+    /// 按键对应的命令, 只查找在`scope`界面生效的命令. 弹窗中先查找弹窗的命令
+    ///
+    pub fn command(&self, key: KeyEvent, scope: Scope) -> Option<Command> {
+        let chord = Chord::from(key);
+        let find = |scope: Scope| {
+            self.bindings
+                .iter()
+                .find(|(bound, command)| {
+                    *bound == chord
+                        && (command.scope() == scope || command.scope() == Scope::Global)
+                })
+                .map(|(_, command)| *command)
+        };
+        match scope {
+            Scope::Popup => find(Scope::Popup).or_else(|| find(Scope::Main)),
+            scope => find(scope),
+        }
+    }
+
+    /// 命令绑定的所有按键
+    pub fn chords(&self, command: Command) -> Vec<Chord> {
+        self.bindings
+            .iter()
+            .filter(|(_, bound)| *bound == command)
+            .map(|(chord, _)| *chord)
+            .collect()
+    }
+
+    /// 命令绑定的第一个按键, 点击按钮时模拟按下它. 没有绑定时为`None`
+    pub fn key(&self, command: Command) -> Option<KeyEvent> {
+        self.chords(command)
+            .first()
+            .map(|chord| KeyEvent::new(chord.code, chord.modifiers))
+    }
+}
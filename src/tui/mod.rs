@@ -1,6 +1,11 @@
 //! tui 模式
 pub mod action;
+pub mod animation;
+pub mod clock;
 pub mod controller;
 pub mod event;
+pub mod keymap;
+pub mod palette;
+pub mod theme;
 pub mod ui;
 pub mod widgets;
@@ -0,0 +1,176 @@
+//! 命令面板
+//!
+//! 按`:`打开, 在状态栏的位置输入命令, 如`:new`, `:seed 42`, `:hard on`, `:load game.wordle`.
+//! tab补全命令名和参数, 回车执行, esc或删空输入后关闭. 执行失败时面板保持打开并显示错误.
+use std::{fs, path::PathBuf, str::FromStr};
+
+use crate::{
+    error::{Result, WordError},
+    t,
+};
+
+/// 命令名及其参数, 按补全候选的顺序排列
+pub const COMMANDS: [(&str, &str); 12] = [
+    ("new", ""),
+    ("hint", ""),
+    ("stats", ""),
+    ("share", ""),
+    ("seed", "<n>"),
+    ("hard", "on|off"),
+    ("load", "<file>"),
+    ("quit", ""),
+    ("help", ""),
+    ("settings", ""),
+    ("theme", ""),
+    ("undo", ""),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteCommand {
+    New,
+    Hint,
+    Stats,
+    Share,
+    // 以该种子随机生成答案, 开始新的一局
+    Seed(u64),
+    Hard(bool),
+    // 加载对局记录并回放
+    Load(PathBuf),
+    Quit,
+    Help,
+    Settings,
+    Theme,
+    Undo,
+}
+
+impl FromStr for PaletteCommand {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (name, arg) = match s.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (s, ""),
+        };
+        let args = COMMANDS
+            .iter()
+            .find(|(command, _)| *command == name)
+            .map(|(_, args)| *args)
+            .ok_or_else(|| WordError::CustomError(t!("palette.unknown", name)))?;
+        if args.is_empty() != arg.is_empty() {
+            return Err(usage(name, args));
+        }
+        Ok(match name {
+            "new" => PaletteCommand::New,
+            "hint" => PaletteCommand::Hint,
+            "stats" => PaletteCommand::Stats,
+            "share" => PaletteCommand::Share,
+            "seed" => PaletteCommand::Seed(
+                arg.parse()
+                    .map_err(|_| WordError::CustomError(t!("palette.invalid_seed", arg)))?,
+            ),
+            "hard" => match arg {
+                "on" => PaletteCommand::Hard(true),
+                "off" => PaletteCommand::Hard(false),
+                _ => return Err(usage(name, args)),
+            },
+            "load" => PaletteCommand::Load(PathBuf::from(arg)),
+            "quit" => PaletteCommand::Quit,
+            "help" => PaletteCommand::Help,
+            "settings" => PaletteCommand::Settings,
+            "theme" => PaletteCommand::Theme,
+            "undo" => PaletteCommand::Undo,
+            _ => return Err(WordError::CustomError(t!("palette.unknown", name))),
+        })
+    }
+}
+
+fn usage(name: &str, args: &str) -> WordError {
+    WordError::CustomError(t!("palette.usage", format!("{} {}", name, args).trim_end()))
+}
+
+/// 命令面板的输入
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Palette {
+    pub input: String,
+    // 上一次执行失败的原因, 修改输入后清除
+    pub error: Option<String>,
+}
+
+impl Palette {
+    pub fn push(&mut self, ch: char) {
+        self.input.push(ch);
+        self.error = None;
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.error = None;
+    }
+
+    ///
+    /// 当前输入的补全候选(完整的输入): 命令名, `hard`的开关, `load`的文件路径
+    ///
+    pub fn completions(&self) -> Vec<String> {
+        let Some((name, arg)) = self.input.split_once(' ') else {
+            return COMMANDS
+                .iter()
+                .filter(|(command, _)| command.starts_with(self.input.as_str()))
+                .map(|(command, args)| {
+                    let space = if args.is_empty() { "" } else { " " };
+                    format!("{}{}", command, space)
+                })
+                .collect();
+        };
+        match name {
+            "hard" => ["on", "off"]
+                .iter()
+                .filter(|value| value.starts_with(arg))
+                .map(|value| format!("{} {}", name, value))
+                .collect(),
+            "load" => {
+                // 按最后一个`/`分为目录和文件名前缀
+                let (dir, prefix) = match arg.rfind('/') {
+                    Some(index) => arg.split_at(index + 1),
+                    None => ("", arg),
+                };
+                let Ok(entries) = fs::read_dir(if dir.is_empty() { "." } else { dir }) else {
+                    return vec![];
+                };
+                let mut paths = entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let file_name = entry.file_name().into_string().ok()?;
+                        let slash = if entry.path().is_dir() { "/" } else { "" };
+                        (file_name.starts_with(prefix) && !file_name.starts_with('.'))
+                            .then(|| format!("{} {}{}{}", name, dir, file_name, slash))
+                    })
+                    .collect::<Vec<_>>();
+                paths.sort();
+                paths
+            }
+            _ => vec![],
+        }
+    }
+
+    /// 补全到所有候选的公共前缀
+    pub fn complete(&mut self) {
+        let completions = self.completions();
+        let Some(first) = completions.first() else {
+            return;
+        };
+        let mut prefix = first.as_str();
+        for completion in &completions[1..] {
+            let len = prefix
+                .char_indices()
+                .zip(completion.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(prefix.len().min(completion.len()), |((index, _), _)| index);
+            prefix = &prefix[..len];
+        }
+        if prefix.len() > self.input.len() {
+            self.input = prefix.to_string();
+            self.error = None;
+        }
+    }
+}
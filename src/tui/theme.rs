@@ -0,0 +1,279 @@
+//! tui 配色主题
+//!
+//! 内置 dark, light, high-contrast, color-blind(橙/蓝) 和 mono 主题, 也可以从 json 文件加载.
+//! 文件中未给出的颜色沿用 dark 主题, 颜色写法同`ratatui::style::Color`的解析, 例如
+//! `"lightcyan"`, `"#f5793a"`, `"208"`.
+//! 设置了`NO_COLOR`环境变量时只使用 mono 主题, 以粗体/反色/下划线区分字母状态.
+use std::{fs, path::Path};
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{
+    error::{Result, WordError},
+    state::{no_color, LetterState},
+    t,
+};
+
+/// 可以在运行时切换的内置主题
+pub const BUILTIN_THEMES: [&str; 5] = ["dark", "light", "high-contrast", "color-blind", "mono"];
+
+/// 方块的前景色, 背景色和修饰
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub struct TileColors {
+    #[serde(deserialize_with = "color")]
+    pub fg: Color,
+    #[serde(deserialize_with = "color")]
+    pub bg: Color,
+    #[serde(skip)]
+    pub modifier: Modifier,
+}
+
+impl TileColors {
+    pub const fn new(fg: Color, bg: Color) -> Self {
+        TileColors {
+            fg,
+            bg,
+            modifier: Modifier::empty(),
+        }
+    }
+
+    pub fn style(&self) -> Style {
+        Style::default()
+            .fg(self.fg)
+            .bg(self.bg)
+            .add_modifier(self.modifier)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+    // 字母状态对应的方块颜色
+    pub correct: TileColors,
+    pub present: TileColors,
+    pub absent: TileColors,
+    pub unknown: TileColors,
+    // 未使用过的键盘按键
+    pub key: TileColors,
+    // 弹窗
+    pub popup: TileColors,
+    #[serde(deserialize_with = "color")]
+    pub border: Color,
+    #[serde(deserialize_with = "color")]
+    pub title: Color,
+    // 普通提示文字
+    #[serde(deserialize_with = "color")]
+    pub text: Color,
+    // 状态栏
+    #[serde(deserialize_with = "color")]
+    pub status: Color,
+    #[serde(deserialize_with = "color")]
+    pub success: Color,
+    #[serde(deserialize_with = "color")]
+    pub error: Color,
+    #[serde(deserialize_with = "color")]
+    pub hint: Color,
+    // 次要的操作说明
+    #[serde(deserialize_with = "color")]
+    pub muted: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Theme {
+            name: "dark".to_string(),
+            correct: TileColors::new(Color::White, Color::Green),
+            present: TileColors::new(Color::White, Color::Yellow),
+            absent: TileColors::new(Color::White, Color::Red),
+            unknown: TileColors::new(Color::White, Color::DarkGray),
+            key: TileColors::new(Color::White, Color::DarkGray),
+            popup: TileColors::new(Color::White, Color::DarkGray),
+            border: Color::LightCyan,
+            title: Color::LightGreen,
+            text: Color::LightYellow,
+            status: Color::LightGreen,
+            success: Color::LightGreen,
+            error: Color::Red,
+            hint: Color::LightCyan,
+            muted: Color::DarkGray,
+        }
+    }
+
+    pub fn light() -> Self {
+        Theme {
+            name: "light".to_string(),
+            correct: TileColors::new(Color::White, Color::Rgb(106, 170, 100)),
+            present: TileColors::new(Color::White, Color::Rgb(201, 180, 88)),
+            absent: TileColors::new(Color::White, Color::Rgb(120, 124, 126)),
+            unknown: TileColors::new(Color::Black, Color::Rgb(211, 214, 218)),
+            key: TileColors::new(Color::Black, Color::Rgb(211, 214, 218)),
+            popup: TileColors::new(Color::Black, Color::Rgb(238, 238, 238)),
+            border: Color::Blue,
+            title: Color::Rgb(0, 110, 0),
+            text: Color::Black,
+            status: Color::Blue,
+            success: Color::Rgb(0, 110, 0),
+            error: Color::Rgb(190, 0, 0),
+            hint: Color::Magenta,
+            muted: Color::Gray,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme {
+            name: "high-contrast".to_string(),
+            correct: TileColors::new(Color::Black, Color::LightGreen),
+            present: TileColors::new(Color::Black, Color::LightYellow),
+            absent: TileColors::new(Color::White, Color::Black),
+            unknown: TileColors::new(Color::Black, Color::White),
+            key: TileColors::new(Color::Black, Color::White),
+            popup: TileColors::new(Color::White, Color::Black),
+            border: Color::White,
+            title: Color::White,
+            text: Color::White,
+            status: Color::White,
+            success: Color::LightGreen,
+            error: Color::LightRed,
+            hint: Color::LightCyan,
+            muted: Color::Gray,
+        }
+    }
+
+    /// 橙/蓝配色, 红绿色盲也能区分
+    pub fn color_blind() -> Self {
+        Theme {
+            name: "color-blind".to_string(),
+            correct: TileColors::new(Color::White, Color::Rgb(245, 121, 58)),
+            present: TileColors::new(Color::White, Color::Rgb(133, 192, 249)),
+            absent: TileColors::new(Color::White, Color::Rgb(58, 58, 60)),
+            success: Color::Rgb(245, 121, 58),
+            error: Color::Rgb(245, 121, 58),
+            hint: Color::Rgb(133, 192, 249),
+            ..Theme::dark()
+        }
+    }
+
+    /// 不使用颜色, 用修饰区分字母状态
+    pub fn mono() -> Self {
+        let plain = TileColors::new(Color::Reset, Color::Reset);
+        Theme {
+            name: "mono".to_string(),
+            correct: TileColors {
+                modifier: Modifier::REVERSED,
+                ..plain
+            },
+            present: TileColors {
+                modifier: Modifier::UNDERLINED,
+                ..plain
+            },
+            absent: TileColors {
+                modifier: Modifier::DIM,
+                ..plain
+            },
+            unknown: plain,
+            key: plain,
+            popup: plain,
+            border: Color::Reset,
+            title: Color::Reset,
+            text: Color::Reset,
+            status: Color::Reset,
+            success: Color::Reset,
+            error: Color::Reset,
+            hint: Color::Reset,
+            muted: Color::Reset,
+        }
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "color-blind" => Some(Theme::color_blind()),
+            "mono" => Some(Theme::mono()),
+            _ => None,
+        }
+    }
+
+    ///
+    /// 从json文件加载主题, 未指定名称时使用文件名
+    ///
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut theme: Theme = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| WordError::CustomError(t!("error.theme_file", e)))?;
+        if theme.name == Theme::dark().name {
+            if let Some(stem) = path.file_stem() {
+                theme.name = stem.to_string_lossy().to_string();
+            }
+        }
+        Ok(theme)
+    }
+
+    ///
+    /// 按`--theme`选择主题: 内置主题名或主题文件路径. 设置了`NO_COLOR`时总是使用mono
+    ///
+    pub fn resolve(theme: Option<&str>) -> Result<Self> {
+        if no_color() {
+            return Ok(Theme::mono());
+        }
+        match theme {
+            None => Ok(Theme::dark()),
+            Some(name) => match Theme::builtin(name) {
+                Some(theme) => Ok(theme),
+                None => Theme::load(name),
+            },
+        }
+    }
+
+    ///
+    /// 运行时切换到下一个内置主题, 设置了`NO_COLOR`时保持不变
+    ///
+    pub fn next(&self) -> Self {
+        if no_color() {
+            return self.clone();
+        }
+        let index = BUILTIN_THEMES
+            .iter()
+            .position(|name| *name == self.name)
+            .map_or(0, |index| (index + 1) % BUILTIN_THEMES.len());
+        Theme::builtin(BUILTIN_THEMES[index]).unwrap_or_default()
+    }
+
+    pub fn tile(&self, state: &LetterState) -> TileColors {
+        match state {
+            LetterState::G => self.correct,
+            LetterState::Y => self.present,
+            LetterState::R => self.absent,
+            LetterState::X => self.unknown,
+        }
+    }
+
+    /// 字母方块的样式
+    pub fn tile_style(&self, state: &LetterState) -> Style {
+        self.tile(state).style().add_modifier(Modifier::BOLD)
+    }
+
+    pub fn border_style(&self) -> Style {
+        Style::default().fg(self.border)
+    }
+
+    pub fn popup_style(&self) -> Style {
+        self.popup.style()
+    }
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Color, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value
+        .parse()
+        .map_err(|_| de::Error::custom(format!("unknown color `{}`", value)))
+}
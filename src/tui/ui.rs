@@ -1,23 +1,56 @@
+use std::rc::Rc;
+
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     prelude::*,
     style::Color,
     style::Style,
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{
+        block::Title, Bar, BarChart, BarGroup, Block, Borders, Cell, Clear, Paragraph, Row, Table,
+        Wrap,
+    },
 };
 
 use crate::{
+    analysis::GameAnalysis,
+    challenge::Challenge,
+    settings::{Autocomplete, WORD_LENGTHS},
     state::LetterState,
+    t,
     word::Word,
     wordle::{CheckResult, Wordle, MAX_RETRY_TIMES},
 };
 
-use super::widgets::{Keyboard, Theme};
+use super::{
+    action::{animations_enabled, next_game_hard},
+    animation::{Animation, TileFrame},
+    keymap::Command,
+    palette::Palette,
+    theme::Theme,
+    widgets::{Button, Keyboard},
+};
+
+/// 结束弹窗中的按钮及其对应的命令
+pub const GAME_OVER_BUTTONS: [(&str, Command); 4] = [
+    ("ui.button.new", Command::Submit),
+    ("ui.button.review", Command::Review),
+    ("ui.button.share", Command::Share),
+    ("ui.button.quit", Command::Quit),
+];
+
+/// 完整显示主界面所需的最小终端大小
+pub const MIN_WIDTH: u16 = 50;
+pub const MIN_HEIGHT: u16 = 29;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum UiState {
     #[default]
     Init,
     Main(MainState),
+    // 助手模式, 记录外部游戏的反馈
+    Assist,
+    // 反向模式, 电脑猜测玩家想好的词
+    Reverse,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -26,10 +59,37 @@ pub enum MainState {
     Main,
     Difficult,
     GameOver,
+    // 赛后复盘
+    Review,
+    // 练习模式的分支树
+    Branches,
+    // 帮助, 统计和设置界面
+    Help,
+    Stats,
+    Settings,
 }
 
 pub fn ui<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>, keyboards: &[Vec<Keyboard>]) {
+    let theme = &wordle.theme;
     let area = frame.size();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        let text = t!(
+            "ui.too_small",
+            area.width,
+            area.height,
+            MIN_WIDTH,
+            MIN_HEIGHT
+        );
+        let lines = (area.height / 2).saturating_sub(1);
+        frame.render_widget(
+            Paragraph::new(format!("{}{}", "\n".repeat(lines as usize), text))
+                .style(fg(theme.error))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            area,
+        );
+        return;
+    }
     match wordle.ui_state {
         UiState::Init => {
             let layout = Layout::new()
@@ -41,12 +101,12 @@ pub fn ui<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>, keyboards: &[Ve
                 ])
                 .split(area);
             frame.render_widget(
-                Paragraph::new("请输入指定猜测的词!")
-                    .style(Style::default().fg(Color::Green))
+                Paragraph::new(t!("ui.init.title"))
+                    .style(fg(theme.success))
                     .block(
                         Block::default()
                             .borders(Borders::ALL)
-                            .border_style(Style::default().fg(Color::LightCyan)),
+                            .border_style(theme.border_style()),
                     ),
                 layout[0],
             );
@@ -54,90 +114,87 @@ pub fn ui<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>, keyboards: &[Ve
                 Paragraph::new(wordle.final_word.to_string()).block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::LightCyan)),
+                        .border_style(theme.border_style()),
                 ),
                 layout[1],
             );
 
-            let ws = wordle.final_word.to_string();
-            let ws = format!(
-                "输入的猜测词无效, 请按 <Backspace> 键重新输入猜测词! {}",
-                ws
-            );
+            let ws = t!("ui.init.invalid", wordle.final_word);
             let status = if !wordle.final_word.is_full() {
-                "猜测词未设置或者正在输入中!".yellow()
+                Span::styled(t!("ui.init.typing"), fg(theme.text))
             } else if wordle.is_final_word_valid() {
-                "输入的猜测词有效, 请按 <Enter> 键进入游戏!".green()
+                Span::styled(t!("ui.init.valid"), fg(theme.success))
             } else {
-                ws.red()
+                Span::styled(ws, fg(theme.error))
             };
-            let lines = vec!["状态: ".green(), status];
+            let mut lines = vec![Span::styled(t!("ui.status"), fg(theme.status)), status];
+            if wordle.final_word.is_full() && wordle.is_final_word_valid() {
+                // 分享挑战码, 朋友无需看到答案即可玩同一个词
+                let challenge =
+                    Challenge::new(&wordle.final_word.to_string(), wordle.opt.difficult);
+                lines.push(Span::styled(
+                    t!("ui.init.challenge", challenge),
+                    fg(theme.hint),
+                ));
+            }
             frame.render_widget(
                 Paragraph::new(Line::from(lines)).block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::LightCyan)),
+                        .border_style(theme.border_style()),
                 ),
                 layout[2],
             );
         }
+        UiState::Assist => {
+            let status = if wordle.is_game_over() {
+                Span::styled(t!("ui.assist.solved"), fg(theme.success))
+            } else if let Some(ref message) = wordle.states.message {
+                Span::styled(message.clone(), fg(theme.error))
+            } else {
+                Span::styled(t!("ui.assist.help"), fg(theme.text))
+            };
+            render_feedback_mode(wordle, frame, t!("ui.assist.title"), Line::from(status));
+        }
+        UiState::Reverse => {
+            let status = if let Some(ref message) = wordle.states.message {
+                Span::styled(message.clone(), fg(theme.error))
+            } else if wordle.reverse_solved() {
+                Span::styled(
+                    t!("ui.reverse.solved", wordle.history_words.len()),
+                    fg(theme.success),
+                )
+            } else if wordle.is_game_over() {
+                Span::styled(t!("ui.reverse.failed", MAX_RETRY_TIMES), fg(theme.error))
+            } else {
+                Span::styled(t!("ui.reverse.help"), fg(theme.text))
+            };
+            render_feedback_mode(wordle, frame, t!("ui.reverse.title"), Line::from(status));
+        }
         UiState::Main(main_state) => {
+            let clock = if wordle.clock.paused {
+                t!("ui.main.paused", wordle.clock.display())
+            } else {
+                t!("ui.main.clock", wordle.clock.display())
+            };
             let block = Block::new()
-                .title("猜词游戏")
+                .title(t!("ui.main.title"))
+                .title(Title::from(clock).alignment(Alignment::Right))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::LightCyan))
-                .title_style(Style::default().fg(Color::LightGreen));
-            let inner = block.inner(frame.size());
+                .border_style(theme.border_style())
+                .title_style(fg(theme.title));
             frame.render_widget(block, frame.size());
-            let layout = Layout::default()
-                .direction(ratatui::prelude::Direction::Vertical)
-                .constraints(vec![
-                    Constraint::Min(15),
-                    Constraint::Min(9),
-                    Constraint::Length(3),
-                ])
-                .split(inner);
-            let gussing_area_block = Block::new()
-                .title("猜测词输入区")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::LightCyan))
-                .title_style(Style::default().fg(Color::Red));
-            let gussing_inner = gussing_area_block.inner(layout[0]);
-            frame.render_widget(gussing_area_block, layout[0]);
-            let mut row_constraint = (0..6)
-                .into_iter()
-                .flat_map(|_| [Constraint::Length(1), Constraint::Length(1)])
-                .collect::<Vec<_>>();
-            row_constraint.push(Constraint::Min(0));
-            let table_row_layout = Layout::default()
-                .direction(ratatui::prelude::Direction::Vertical)
-                .constraints(row_constraint)
-                .split(gussing_inner);
-
-            let default_word = Word::whitespace_word_for_render();
-            for idx in wordle.history_words.len()..MAX_RETRY_TIMES as usize {
-                render_buttons(frame, table_row_layout[idx * 2], &default_word);
-            }
-            // 历史记录渲染
-            for (idx, word) in wordle.history_words.iter().enumerate() {
-                render_buttons(frame, table_row_layout[idx * 2], word);
-            }
-
-            // 当前行渲染
-            render_buttons(
-                frame,
-                table_row_layout[(wordle.states.current_try_times as usize) * 2],
-                &wordle.states.current_word,
-            );
+            let layout = main_layout(frame.size());
+            render_guesses(wordle, frame, layout[0]);
 
             // status render
-            let s = format!(
-                "-> 状态: [{:?}] 困难模式: [{}], 随机模式: [{}] | ",
-                main_state,
-                if wordle.opt.difficult { "是" } else { "否" },
-                if wordle.opt.random { "是" } else { "否" }
+            let s = t!(
+                "ui.main.status",
+                format!("{:?}", main_state),
+                yes_no(wordle.opt.difficult),
+                yes_no(wordle.opt.random)
             );
-            let status_bar = s.light_green();
+            let status_bar = Span::styled(s, fg(theme.status));
             let mut status = vec![status_bar];
             let current_times = wordle.states.current_try_times;
             let current_word = wordle.states.current_word.to_string();
@@ -145,92 +202,121 @@ pub fn ui<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>, keyboards: &[Ve
             if let Some(result) = wordle.states.current_checked_result {
                 match result {
                     CheckResult::InValid => {
-                        s = format!("输入的猜测词 [{}] 不在词库中!", current_word);
+                        s = t!("ui.main.not_in_list", current_word);
                     }
                     CheckResult::Success => {
-                        status.push(
-                            "您猜对了, 请按 <Enter> 键继续, 按 <Esc> 退出游戏!".light_green(),
-                        );
+                        status.push(Span::styled(t!("ui.main.success"), fg(theme.success)));
                     }
                     CheckResult::Wrong => {
-                        s = format!("第 {} 次猜测错误, 请继续!", current_times);
+                        s = t!("ui.main.wrong", current_times);
                     }
                     CheckResult::Difficult => {}
                 }
             } else {
-                s = "等待用户输入猜测词!".to_string();
+                s = t!("ui.main.waiting").to_string();
             }
-            status.push(s.light_yellow());
-            let footer = Line::from(status);
+            status.push(Span::styled(s, fg(theme.text)));
+            if wordle.opt.practice {
+                status.push(Span::styled(t!("ui.main.practice"), fg(theme.muted)));
+            }
+            if let (Some(ref message), MainState::Main) = (&wordle.states.message, main_state) {
+                status.push(Span::styled(format!(" | {}", message), fg(theme.error)));
+            }
+            if !wordle.replay_queue.is_empty() {
+                status.push(Span::styled(t!("ui.main.replaying"), fg(theme.hint)));
+            } else if let Some(ref hint) = wordle.states.hint {
+                status.push(Span::styled(t!("ui.main.hint", hint), fg(theme.hint)));
+            } else if wordle.states.hint_pending {
+                status.push(Span::styled(t!("ui.main.hint_pending"), fg(theme.muted)));
+            } else if !wordle.is_game_over() {
+                status.push(Span::styled(t!("ui.main.hint_key"), fg(theme.muted)));
+            }
+            status.push(Span::styled(t!("ui.main.screens"), fg(theme.muted)));
+            let footer = match wordle.palette {
+                Some(ref palette) => palette_line(theme, palette),
+                None => Line::from(status),
+            };
             frame.render_widget(Paragraph::new(footer), layout[2]);
 
             // render keyboards
             render_keyboards(wordle, frame, keyboards, layout[1]);
+            if let MainState::Main = main_state {
+                render_completions(wordle, frame, layout[0]);
+            }
             if let MainState::GameOver = main_state {
                 let popup_block = Block::new()
-                    .title("提示")
-                    .title_style(Style::default().fg(Color::Yellow))
+                    .title(t!("ui.popup.title"))
+                    .title_style(fg(theme.title))
                     .title_alignment(Alignment::Center)
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Black))
-                    .style(Style::default().bg(Color::DarkGray).fg(Color::White));
-                let paragraph = Paragraph::new("按 <Enter> 键开始新游戏 / 按 <Esc> 键退出游戏!")
-                    .alignment(Alignment::Center)
-                    .block(popup_block);
-                let area = centered_rect(40, 10, frame.size());
+                    .border_style(fg(theme.popup.fg))
+                    .style(theme.popup_style());
+                let mut lines = vec![Line::from(t!("ui.gameover.help"))];
+                if let Some(ref message) = wordle.states.message {
+                    lines.push(Line::from(Span::styled(message.clone(), fg(theme.success))));
+                }
+                let paragraph = Paragraph::new(lines)
+                    .wrap(Wrap { trim: true })
+                    .alignment(Alignment::Center);
+                let area = centered_rect(50, 20, frame.size());
                 frame.render_widget(Clear, area);
-                frame.render_widget(paragraph, area);
+                frame.render_widget(popup_block, area);
+                let (text_area, buttons) = game_over_layout(frame.size());
+                frame.render_widget(paragraph, text_area);
+                for (button, (label, _)) in buttons.iter().zip(GAME_OVER_BUTTONS) {
+                    frame.render_widget(Button::new(t!(label)).theme(theme.key), *button);
+                }
+            }
+
+            if let MainState::Review = main_state {
+                render_review(theme, wordle.states.analysis.as_ref(), frame);
+            }
+
+            match main_state {
+                MainState::Branches => render_branches(wordle, frame),
+                MainState::Help => render_help(wordle, frame),
+                MainState::Stats => render_stats(wordle, frame),
+                MainState::Settings => render_settings(wordle, frame),
+                _ => {}
             }
 
             if let MainState::Difficult = main_state {
                 // 困难模式下, 需要popup 并按回车键确认退出!
                 let block = Block::new()
-                    .bg(Color::DarkGray)
-                    .fg(Color::White)
+                    .style(theme.popup_style())
                     .borders(Borders::ALL)
-                    .title("警告")
-                    .title_style(Style::default().fg(Color::Red))
+                    .title(t!("ui.difficult.title"))
+                    .title_style(fg(theme.error))
                     .title_alignment(Alignment::Left);
                 let area = centered_rect(60, 20, frame.size());
-                let inner = block.inner(area);
                 // frame.render_widget(Clear, area);
                 frame.render_widget(block, area);
-                let layout = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Min(0), Constraint::Length(3)])
-                    .split(inner);
+                let layout = difficult_layout(frame.size());
 
                 let greens = wordle.get_diffcult_errors_in_green();
-                let mut text = String::new();
-                if greens.is_empty() {
-                    text.push_str("必须包含以下: ");
-                    wordle
+                let text = if greens.is_empty() {
+                    let letters = wordle
                         .get_diffcult_errors_in_yellow()
                         .iter()
-                        .for_each(|(_, letter)| {
-                            text.push(letter.0);
-                            text.push(',');
-                        });
-                    text.push_str("字符");
+                        .map(|(_, letter)| letter.0.to_string())
+                        .collect::<Vec<_>>();
+                    t!("ui.difficult.contain", letters.join(","))
                 } else {
-                    text.push_str("以下位置必须一一对应: ");
-                    greens.iter().for_each(|(index, letter)| {
-                        let s = format!("{} -> {}, ", index + 1, letter.0);
-                        text.push_str(s.as_str());
-                    });
-                }
-                frame.render_widget(
-                    Paragraph::new(text).style(Style::default().fg(Color::Red)),
-                    layout[0],
-                );
+                    let positions = greens
+                        .iter()
+                        .map(|(index, letter)| format!("{} -> {}", index + 1, letter.0))
+                        .collect::<Vec<_>>();
+                    t!("ui.difficult.position", positions.join(", "))
+                };
+                frame.render_widget(Paragraph::new(text).style(fg(theme.error)), layout[0]);
                 frame.render_widget(
-                    Paragraph::new("按 <Enter> 键继续")
+                    Paragraph::new(t!("ui.difficult.continue"))
                         .alignment(Alignment::Center)
-                        .style(Style::default().bg(Color::DarkGray).fg(Color::White))
+                        .style(theme.popup_style())
                         .block(
                             Block::new()
                                 .borders(Borders::ALL)
-                                .border_style(Style::default().fg(Color::White)),
+                                .border_style(fg(theme.popup.fg)),
                         ),
                     layout[1],
                 );
@@ -239,69 +325,666 @@ pub fn ui<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>, keyboards: &[Ve
     }
 }
 
-fn render_keyboards<B: Backend>(
+/// 主界面布局: 猜测区, 键盘区和状态栏
+fn main_layout(area: Rect) -> Rc<[Rect]> {
+    let inner = Block::new().borders(Borders::ALL).inner(area);
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+            Constraint::Min(15),
+            Constraint::Min(9),
+            Constraint::Length(3),
+        ])
+        .split(inner)
+}
+
+/// 结束弹窗布局: 提示文字区和一排按钮
+fn game_over_layout(area: Rect) -> (Rect, Vec<Rect>) {
+    let inner = Block::new()
+        .borders(Borders::ALL)
+        .inner(centered_rect(50, 20, area));
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+    let buttons = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            GAME_OVER_BUTTONS
+                .iter()
+                .map(|_| Constraint::Ratio(1, GAME_OVER_BUTTONS.len() as u32))
+                .collect::<Vec<_>>(),
+        )
+        .split(layout[1])
+        .iter()
+        // 按钮之间留出一列空隙
+        .map(|rect| Rect {
+            width: rect.width.saturating_sub(1),
+            ..*rect
+        })
+        .collect();
+    (layout[0], buttons)
+}
+
+/// 困难模式警告弹窗布局: 提示文字区和确认按钮
+fn difficult_layout(area: Rect) -> Rc<[Rect]> {
+    let inner = Block::new()
+        .borders(Borders::ALL)
+        .inner(centered_rect(60, 20, area));
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(inner)
+}
+
+/// 键盘区中每个按键的位置, 与`keyboards`一一对应
+fn keyboard_areas(area: Rect, keyboards: &[Vec<Keyboard>]) -> Vec<Vec<Rect>> {
+    let inner = Block::new().borders(Borders::ALL).inner(area);
+    // 每行按键之间空一行, 行数由键盘布局决定
+    let mut constraints = (0..keyboards.len())
+        .flat_map(|_| [Constraint::Length(1), Constraint::Length(1)])
+        .collect::<Vec<_>>();
+    constraints.push(Constraint::Min(0));
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(inner);
+    keyboards
+        .iter()
+        .map(|keys| {
+            let row = keys.first().map_or(0, |key| key.row as usize);
+            let mut constraints: Vec<Constraint> = keys
+                .iter()
+                .flat_map(|key| [Constraint::Length(key.size), Constraint::Length(1)])
+                .collect();
+            constraints.push(Constraint::Min(0));
+            let key_layout = Layout::new()
+                .direction(Direction::Horizontal)
+                .constraints(constraints)
+                .split(layout[row * 2]);
+            (0..keys.len()).map(|idx| key_layout[idx * 2]).collect()
+        })
+        .collect()
+}
+
+///
+/// 鼠标点击位置对应的按键, 点击屏幕键盘和弹窗按钮等同于按下对应的键.
+/// 回车, 退格和弹窗按钮按下当前绑定到对应命令的按键.
+/// 布局按`area`(当前终端大小)重新计算, 因此调整窗口大小后仍然准确
+///
+pub fn hit_test(
     wordle: &Wordle,
-    frame: &mut Frame<'_, B>,
-    keyboards: &[Vec<Keyboard>],
     area: Rect,
-) {
+    keyboards: &[Vec<Keyboard>],
+    column: u16,
+    row: u16,
+) -> Option<KeyEvent> {
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        return None;
+    }
+    let position = Rect::new(column, row, 1, 1);
+    let UiState::Main(main_state) = wordle.ui_state else {
+        return None;
+    };
+    match main_state {
+        MainState::Main => keyboard_areas(main_layout(area)[1], keyboards)
+            .iter()
+            .zip(keyboards)
+            .flat_map(|(areas, keys)| areas.iter().zip(keys))
+            .find(|(rect, _)| rect.intersects(position))
+            .and_then(|(_, key)| match key.key_code() {
+                KeyCode::Enter => wordle.keymap.key(Command::Submit),
+                KeyCode::Backspace => wordle.keymap.key(Command::Delete),
+                code => Some(KeyEvent::from(code)),
+            }),
+        MainState::GameOver => game_over_layout(area)
+            .1
+            .iter()
+            .zip(GAME_OVER_BUTTONS)
+            .find(|(rect, _)| rect.intersects(position))
+            .and_then(|(_, (_, command))| wordle.keymap.key(command)),
+        MainState::Difficult => difficult_layout(area)[1]
+            .intersects(position)
+            .then(|| wordle.keymap.key(Command::Submit))
+            .flatten(),
+        MainState::Review
+        | MainState::Branches
+        | MainState::Help
+        | MainState::Stats
+        | MainState::Settings => None,
+    }
+}
+
+/// 分支树: 开局和每个尝试过的猜测, 当前分支以`*`标记
+fn render_branches<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>) {
+    let theme = &wordle.theme;
     let block = Block::new()
-        .title("键盘区")
+        .title(t!("ui.branches.title"))
+        .title_style(fg(theme.title))
+        .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::LightCyan));
+        .border_style(theme.border_style());
+    let area = centered_rect(60, 70, frame.size());
     let inner = block.inner(area);
+    frame.render_widget(Clear, area);
     frame.render_widget(block, area);
     let layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints(vec![
-            Constraint::Length(1),
-            Constraint::Length(1),
-            Constraint::Length(1),
-            Constraint::Length(1),
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let selected_style = Style::default().add_modifier(Modifier::REVERSED);
+    let tree = wordle.branch_tree();
+    let mut lines = vec![Line::styled(
+        t!("ui.branches.start", wordle.final_set.len()),
+        if wordle.states.selected == 0 {
+            selected_style
+        } else {
+            Style::default()
+        },
+    )];
+    for (index, node) in tree.iter().enumerate() {
+        let mut spans = vec![Span::raw(format!(
+            "{}{} ",
+            "  ".repeat(node.depth()),
+            if node.active { "*" } else { " " }
+        ))];
+        spans.extend(
+            node.guess
+                .get_letters()
+                .iter()
+                .map(|letter| Span::styled(letter.0.to_string(), theme.tile_style(&letter.1))),
+        );
+        spans.push(Span::styled(
+            t!("ui.branches.candidates", node.candidates),
+            if wordle.states.selected == index + 1 {
+                selected_style
+            } else {
+                fg(theme.text)
+            },
+        ));
+        lines.push(Line::from(spans));
+    }
+    // 选中的行超出区域时向下滚动
+    let height = layout[0].height as usize;
+    let offset = (wordle.states.selected + 1).saturating_sub(height) as u16;
+    frame.render_widget(Paragraph::new(lines).scroll((offset, 0)), layout[0]);
+    frame.render_widget(
+        Paragraph::new(Span::styled(t!("ui.branches.help"), fg(theme.muted))),
+        layout[1],
+    );
+}
+
+/// 弹窗形式的全屏界面, 返回内容区和底部说明区
+fn render_screen<B: Backend>(
+    theme: &Theme,
+    frame: &mut Frame<'_, B>,
+    title: &str,
+    help: &str,
+) -> Rect {
+    let block = Block::new()
+        .title(title.to_string())
+        .title_style(fg(theme.title))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(theme.border_style());
+    let area = centered_rect(70, 70, frame.size());
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+    frame.render_widget(
+        Paragraph::new(Span::styled(help.to_string(), fg(theme.muted))),
+        layout[1],
+    );
+    layout[0]
+}
+
+/// 帮助界面: 游戏规则, 颜色示例和当前的按键绑定
+fn render_help<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>) {
+    let theme = &wordle.theme;
+    let area = render_screen(theme, frame, t!("ui.help.title"), t!("ui.help.back"));
+    let keys = Command::ALL
+        .iter()
+        .filter_map(|command| {
+            let chords = wordle.keymap.chords(*command);
+            let chords = chords.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+            (!chords.is_empty()).then(|| t!("ui.help.key", chords.join("/"), command.label()))
+        })
+        .collect::<Vec<_>>();
+    let sample = |letter: char, state: LetterState, text: &'static str| {
+        Line::from(vec![
+            Span::styled(format!(" {} ", letter), theme.tile_style(&state)),
+            Span::styled(text, fg(theme.text)),
+        ])
+    };
+    let lines = vec![
+        Line::styled(t!("ui.help.rules"), fg(theme.text)),
+        Line::default(),
+        sample('W', LetterState::G, t!("ui.help.correct")),
+        sample('O', LetterState::Y, t!("ui.help.present")),
+        sample('R', LetterState::R, t!("ui.help.absent")),
+        Line::default(),
+        Line::styled(t!("ui.help.hard"), fg(theme.text)),
+        Line::default(),
+        Line::styled(keys.join(", "), fg(theme.hint)),
+    ];
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
+/// 统计界面: 总局数, 胜率和猜中次数分布
+fn render_stats<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>) {
+    let theme = &wordle.theme;
+    let area = render_screen(theme, frame, t!("ui.stats.title"), t!("ui.help.back"));
+    let statistics = &wordle.statistics;
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
             Constraint::Length(1),
             Constraint::Min(0),
         ])
-        .split(inner);
-    for i in 0..3 {
-        let keys = &keyboards[i];
-        let mut constraints: Vec<Constraint> = keys
-            .iter()
-            .flat_map(|key| [Constraint::Length(key.size), Constraint::Length(1)])
-            .collect();
-        constraints.push(Constraint::Min(0));
-        let key_layout = Layout::new()
+        .split(area);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            t!(
+                "ui.stats.summary",
+                statistics.total,
+                statistics.success_total,
+                format!("{:.0}", statistics.win_rate())
+            ),
+            fg(theme.text),
+        )),
+        layout[0],
+    );
+    frame.render_widget(
+        Paragraph::new(Span::styled(t!("ui.stats.distribution"), fg(theme.title))),
+        layout[1],
+    );
+    let bars = (0..MAX_RETRY_TIMES as usize)
+        .map(|index| {
+            let count = statistics.distribution.get(index).copied().unwrap_or(0);
+            Bar::default()
+                .label(Line::from((index + 1).to_string()))
+                .value(count as u64)
+                .style(fg(theme.correct.bg))
+                .value_style(theme.tile_style(&LetterState::G))
+        })
+        .collect::<Vec<_>>();
+    frame.render_widget(
+        BarChart::default()
             .direction(Direction::Horizontal)
-            .constraints(constraints)
-            .split(layout[i * 2]);
-        for (idx, key) in keys.iter().enumerate() {
+            .bar_width(1)
+            .bar_gap(0)
+            .data(BarGroup::default().bars(&bars)),
+        layout[2],
+    );
+}
+
+/// 设置界面: 困难模式, 主题, 单词长度, 动画和自动补全, 选中的一行反色显示
+fn render_settings<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>) {
+    let theme = &wordle.theme;
+    let area = render_screen(
+        theme,
+        frame,
+        t!("ui.settings.title"),
+        t!("ui.settings.help"),
+    );
+    let settings = &wordle.settings;
+    let rows = [
+        t!("ui.settings.hard", yes_no(next_game_hard(wordle))),
+        t!("ui.settings.theme", theme.name),
+        t!("ui.settings.length", settings.word_length),
+        t!("ui.settings.animations", yes_no(animations_enabled(wordle))),
+        t!(
+            "ui.settings.autocomplete",
+            match settings.autocomplete {
+                Autocomplete::Off => t!("ui.settings.autocomplete.off"),
+                Autocomplete::All => t!("ui.settings.autocomplete.all"),
+                Autocomplete::Consistent => t!("ui.settings.autocomplete.consistent"),
+            }
+        ),
+    ];
+    let mut lines = rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let style = if wordle.states.selected == index {
+                fg(theme.text).add_modifier(Modifier::REVERSED)
+            } else {
+                fg(theme.text)
+            };
+            Line::styled(row, style)
+        })
+        .collect::<Vec<_>>();
+    lines.push(Line::default());
+    if WORD_LENGTHS.len() == 1 {
+        lines.push(Line::styled(t!("ui.settings.length_note"), fg(theme.muted)));
+    }
+    if let Some(ref message) = wordle.states.message {
+        lines.push(Line::styled(message.clone(), fg(theme.hint)));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// 复盘界面: 每次猜测的候选词数, 信息量, 最佳猜测, 技巧分和运气分. 分析还没算完时显示等待提示
+fn render_review<B: Backend>(
+    theme: &Theme,
+    analysis: Option<&GameAnalysis>,
+    frame: &mut Frame<'_, B>,
+) {
+    let block = Block::new()
+        .title(t!("ui.review.title"))
+        .title_style(fg(theme.title))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(theme.border_style());
+    let area = centered_rect(80, 60, frame.size());
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(inner);
+    let Some(analysis) = analysis else {
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(Span::styled(t!("ui.review.pending"), fg(theme.text))),
+                Line::from(Span::styled(t!("ui.review.help"), fg(theme.muted))),
+            ]),
+            inner,
+        );
+        return;
+    };
+
+    let header = Row::new(vec![
+        "#",
+        t!("review.guess"),
+        t!("review.colors"),
+        t!("review.before"),
+        t!("review.after"),
+        t!("review.info"),
+        t!("review.got"),
+        t!("review.best"),
+        t!("review.info"),
+        t!("review.skill"),
+        t!("review.luck"),
+    ])
+    .style(fg(theme.title));
+    let rows = analysis.guesses.iter().enumerate().map(|(index, guess)| {
+        let luck = if guess.luck >= 0.0 {
+            theme.success
+        } else {
+            theme.error
+        };
+        Row::new(vec![
+            Cell::from((index + 1).to_string()),
+            Cell::from(guess.guess.clone()),
+            Cell::from(guess.pattern.clone()),
+            Cell::from(guess.candidates_before.to_string()),
+            Cell::from(guess.candidates_after.to_string()),
+            Cell::from(format!("{:.2}", guess.expected_info)),
+            Cell::from(format!("{:.2}", guess.actual_info)),
+            Cell::from(guess.best_guess.clone()),
+            Cell::from(format!("{:.2}", guess.best_info)),
+            Cell::from(guess.skill.to_string()),
+            Cell::from(format!("{:+.2}", guess.luck)).style(Style::default().fg(luck)),
+        ])
+    });
+    let widths = [
+        Constraint::Length(2),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(5),
+        Constraint::Length(5),
+        Constraint::Length(5),
+        Constraint::Length(5),
+        Constraint::Length(6),
+        Constraint::Length(5),
+        Constraint::Length(4),
+        Constraint::Length(6),
+    ];
+    frame.render_widget(Table::new(rows).header(header).widths(&widths), layout[0]);
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled(format!("{}: ", t!("review.skill")), fg(theme.status)),
+                Span::styled(format!("{}/100", analysis.skill), fg(theme.text)),
+                Span::styled(format!(" {}: ", t!("review.luck")), fg(theme.status)),
+                Span::styled(
+                    t!("review.bits", format!("{:+.2}", analysis.luck)),
+                    fg(theme.text),
+                ),
+            ]),
+            Line::from(Span::styled(t!("ui.review.help"), fg(theme.muted))),
+        ]),
+        layout[1],
+    );
+}
+
+/// 助手模式和反向模式: 猜测区, 候选词及推荐, 状态栏
+fn render_feedback_mode<B: Backend>(
+    wordle: &Wordle,
+    frame: &mut Frame<'_, B>,
+    title: &str,
+    status: Line<'_>,
+) {
+    let theme = &wordle.theme;
+    let block = Block::new()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(theme.border_style())
+        .title_style(fg(theme.title));
+    let inner = block.inner(frame.size());
+    frame.render_widget(block, frame.size());
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+            Constraint::Min(15),
+            Constraint::Min(9),
+            Constraint::Length(3),
+        ])
+        .split(inner);
+    render_guesses(wordle, frame, layout[0]);
+
+    let candidates = wordle.remaining_candidates();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(t!("ui.candidates.remaining"), fg(theme.status)),
+        Span::styled(candidates.len().to_string(), fg(theme.text)),
+    ])];
+    if candidates.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t!("ui.candidates.none"),
+            fg(theme.error),
+        )));
+    } else {
+        lines.push(Line::from(
+            candidates
+                .iter()
+                .take(60)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" "),
+        ));
+    }
+    if !wordle.states.suggestions.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(t!("ui.candidates.suggest"), fg(theme.status)),
+            Span::styled(wordle.states.suggestions.join(" "), fg(theme.hint)),
+        ]));
+    }
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+            Block::new()
+                .title(t!("ui.candidates.title"))
+                .borders(Borders::ALL)
+                .border_style(theme.border_style()),
+        ),
+        layout[1],
+    );
+
+    frame.render_widget(Paragraph::new(status), layout[2]);
+}
+
+/// 渲染猜测区: 历史猜测, 当前输入和剩余的空行
+fn render_guesses<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>, area: Rect) {
+    let theme = &wordle.theme;
+    let gussing_area_block = Block::new()
+        .title(t!("ui.guesses.title"))
+        .borders(Borders::ALL)
+        .border_style(theme.border_style())
+        .title_style(fg(theme.title));
+    let gussing_inner = gussing_area_block.inner(area);
+    frame.render_widget(gussing_area_block, area);
+    let mut row_constraint = (0..6)
+        .flat_map(|_| [Constraint::Length(1), Constraint::Length(1)])
+        .collect::<Vec<_>>();
+    row_constraint.push(Constraint::Min(0));
+    let table_row_layout = Layout::default()
+        .direction(ratatui::prelude::Direction::Vertical)
+        .constraints(row_constraint)
+        .split(gussing_inner);
+
+    let default_word = Word::whitespace_word_for_render();
+    let animation = |idx: usize| wordle.animation.as_ref().filter(|a| a.row == idx);
+    for idx in wordle.history_words.len()..MAX_RETRY_TIMES as usize {
+        render_buttons(theme, frame, table_row_layout[idx * 2], &default_word, None);
+    }
+    // 历史记录渲染
+    for (idx, word) in wordle.history_words.iter().enumerate() {
+        render_buttons(
+            theme,
+            frame,
+            table_row_layout[idx * 2],
+            word,
+            animation(idx),
+        );
+    }
+
+    // 当前行渲染
+    let current = wordle.states.current_try_times as usize;
+    if current < MAX_RETRY_TIMES as usize {
+        render_buttons(
+            theme,
+            frame,
+            table_row_layout[current * 2],
+            &wordle.states.current_word,
+            animation(current),
+        );
+    }
+}
+
+/// 自动补全弹窗中最多列出的单词数
+pub const COMPLETION_LIMIT: usize = 6;
+
+///
+/// 自动补全弹窗中的单词. 关闭自动补全, 当前行为空或已满, 对局结束或命令面板打开时为空
+///
+pub fn completions(wordle: &Wordle) -> Vec<&str> {
+    let current_word = &wordle.states.current_word;
+    if wordle.settings.autocomplete == Autocomplete::Off
+        || current_word.is_empty()
+        || current_word.is_full()
+        || wordle.is_game_over()
+        || wordle.palette.is_some()
+    {
+        return vec![];
+    }
+    wordle.completions(
+        &current_word.to_string(),
+        wordle.settings.autocomplete == Autocomplete::Consistent,
+        COMPLETION_LIMIT,
+    )
+}
+
+/// 在猜测区右侧, 当前行下方显示自动补全弹窗
+fn render_completions<B: Backend>(wordle: &Wordle, frame: &mut Frame<'_, B>, area: Rect) {
+    let words = completions(wordle);
+    if words.is_empty() {
+        return;
+    }
+    let theme = &wordle.theme;
+    let lines = words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| {
+            let style = if wordle.states.completion == Some(index) {
+                theme.popup_style().add_modifier(Modifier::REVERSED)
+            } else {
+                theme.popup_style()
+            };
+            Line::styled(format!(" {} ", word), style)
+        })
+        .collect::<Vec<_>>();
+    let width = 14.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let row = area.y + 2 + wordle.states.current_try_times as u16 * 2;
+    let popup = Rect {
+        x: area.right().saturating_sub(width + 1),
+        y: row.min(area.bottom().saturating_sub(height)),
+        width,
+        height,
+    };
+    let block = Block::new()
+        .title(t!("ui.completions.title"))
+        .title_style(fg(theme.muted))
+        .borders(Borders::ALL)
+        .border_style(fg(theme.popup.fg))
+        .style(theme.popup_style());
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+fn render_keyboards<B: Backend>(
+    wordle: &Wordle,
+    frame: &mut Frame<'_, B>,
+    keyboards: &[Vec<Keyboard>],
+    area: Rect,
+) {
+    let theme = &wordle.theme;
+    let block = Block::new()
+        .title(t!("ui.keyboard.title"))
+        .borders(Borders::ALL)
+        .border_style(theme.border_style());
+    frame.render_widget(block, area);
+    for (keys, key_areas) in keyboards.iter().zip(keyboard_areas(area, keyboards)) {
+        for (key, key_area) in keys.iter().zip(key_areas) {
             let state = key.letter().map(|key| {
                 wordle
                     .cached_letter_states
                     .iter()
                     .filter(|letter| letter.0.eq_ignore_ascii_case(&key))
-                    .into_iter()
                     .min_by(|s0, s1| s0.1.cmp(&s1.1))
             });
 
             let graph = Paragraph::new(key.text.as_str())
                 .style(if let Some(Some(state)) = state {
-                    get_style(&state.1)
+                    theme.tile_style(&state.1)
                 } else {
-                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                    theme.key.style()
                 })
                 .alignment(Alignment::Center);
-            frame.render_widget(graph, key_layout[idx * 2]);
+            frame.render_widget(graph, key_area);
         }
     }
 }
 
-fn render_buttons<B: Backend>(frame: &mut Frame<'_, B>, area: Rect, word: &Word) {
+fn render_buttons<B: Backend>(
+    theme: &Theme,
+    frame: &mut Frame<'_, B>,
+    area: Rect,
+    word: &Word,
+    animation: Option<&Animation>,
+) {
     let layout = Layout::default()
         .constraints(vec![Constraint::Percentage(80), Constraint::Percentage(20)])
         .direction(Direction::Vertical)
         .split(area);
     let mut col_constraits = (0..5)
-        .into_iter()
         .flat_map(|_| [Constraint::Length(3), Constraint::Length(1)])
         .collect::<Vec<_>>();
     col_constraits.push(Constraint::Min(0));
@@ -310,41 +993,62 @@ fn render_buttons<B: Backend>(frame: &mut Frame<'_, B>, area: Rect, word: &Word)
         .constraints(col_constraits)
         .split(layout[0]);
     for (index, letter) in word.get_letters().iter().enumerate() {
+        let mut area = table_col_layout[index * 2];
+        let mut state = letter.1;
+        if let Some(animation) = animation {
+            area = animation.tile_area(index, area).intersection(frame.size());
+            match animation.tile(index) {
+                TileFrame::Hidden | TileFrame::Flipping(false) => state = LetterState::X,
+                _ => {}
+            }
+        }
         frame.render_widget(
             Paragraph::new(letter.0.to_string())
-                .style(get_style(&letter.1))
+                .style(theme.tile_style(&state))
                 .alignment(Alignment::Center),
-            table_col_layout[index * 2],
+            area,
         );
     }
 }
 
-pub fn get_style(letter_state: &LetterState) -> Style {
-    Style::default()
-        .bg(match letter_state {
-            LetterState::G => Color::Green,
-            LetterState::Y => Color::Yellow,
-            LetterState::R => Color::Red,
-            LetterState::X => Color::DarkGray,
-        })
-        .fg(match letter_state {
-            LetterState::G => Color::White,
-            LetterState::Y => Color::White,
-            LetterState::R => Color::White,
-            LetterState::X => Color::White,
-        })
-        .bold()
+///
+/// 命令面板替换状态栏: 输入和光标, 之后是错误信息或补全候选
+///
+fn palette_line<'a>(theme: &Theme, palette: &'a Palette) -> Line<'a> {
+    let mut spans = vec![
+        Span::styled(":", fg(theme.status)),
+        Span::styled(palette.input.as_str(), fg(theme.text)),
+        Span::styled("_", fg(theme.muted)),
+    ];
+    match palette.error {
+        Some(ref error) => spans.push(Span::styled(format!("  {}", error), fg(theme.error))),
+        None => {
+            let completions = palette.completions();
+            if !completions.is_empty() && completions != [palette.input.as_str()] {
+                let completions = completions.iter().map(|c| c.trim_end()).collect::<Vec<_>>();
+                spans.push(Span::styled(
+                    format!("  {}", completions.join(" ")),
+                    fg(theme.muted),
+                ));
+            }
+        }
+    }
+    Line::from(spans)
 }
 
-pub fn get_theme(letter_state: &LetterState) -> Theme {
-    match letter_state {
-        LetterState::G => Theme::new(Color::White, Color::Green),
-        LetterState::Y => Theme::new(Color::White, Color::Yellow),
-        LetterState::R => Theme::new(Color::White, Color::Red),
-        LetterState::X => Theme::new(Color::White, Color::Black),
+/// 只设置前景色的样式
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        t!("ui.yes")
+    } else {
+        t!("ui.no")
     }
 }
 
+fn fg(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -1,11 +1,14 @@
+use crossterm::event::KeyCode;
 use ratatui::{prelude::*, widgets::*};
 
-use crate::state::LetterState;
+use crate::{layout::KeyboardLayout, state::LetterState};
+
+use super::theme::{Theme, TileColors};
 
 pub struct Button<'a> {
     label: Line<'a>,
     state: LetterState,
-    theme: Theme,
+    theme: TileColors,
 }
 
 impl Button<'_> {
@@ -14,60 +17,18 @@ impl Button<'_> {
         self
     }
 
-    pub fn theme(mut self, theme: Theme) -> Self {
+    pub fn theme(mut self, theme: TileColors) -> Self {
         self.theme = theme;
         self
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct Theme {
-    text: Color,
-    background: Color,
-    _highlight: Color,
-}
-
-/// 默认颜色
-pub const THEME_X: Theme = Theme {
-    text: Color::DarkGray,
-    background: Color::Rgb(164, 174, 196),
-    _highlight: Color::Rgb(196, 203, 221),
-};
-
-pub const THEME_R: Theme = Theme {
-    text: Color::White,
-    background: Color::LightRed,
-    _highlight: Color::Rgb(196, 203, 221),
-};
-
-pub const THEME_Y: Theme = Theme {
-    text: Color::White,
-    background: Color::LightYellow,
-    _highlight: Color::Rgb(196, 203, 221),
-};
-
-pub const THEME_G: Theme = Theme {
-    text: Color::White,
-    background: Color::LightGreen,
-    _highlight: Color::Rgb(196, 203, 221),
-};
-
-impl Theme {
-    pub fn new(fg: Color, bg: Color) -> Self {
-        Theme {
-            background: bg,
-            text: fg,
-            _highlight: Color::Black,
-        }
-    }
-}
-
 impl<'a> Button<'a> {
     pub fn new<T: Into<Line<'a>>>(label: T) -> Self {
         Button {
             label: label.into(),
             state: LetterState::X,
-            theme: THEME_X,
+            theme: Theme::dark().key,
         }
     }
 }
@@ -77,8 +38,9 @@ impl<'a> Widget for Button<'a> {
         buf.set_style(
             area,
             Style::default()
-                .bg(self.theme.background)
-                .fg(self.theme.text),
+                .bg(self.theme.bg)
+                .fg(self.theme.fg)
+                .add_modifier(self.theme.modifier),
         );
 
         let width = (area.width as f32 * 0.8) as u16;
@@ -99,34 +61,35 @@ pub enum KeyboardType {
 }
 
 pub struct Keyboard {
-    _x: u16,
-    _y: u16,
+    // 在键盘区中的行和列
+    pub row: u16,
+    pub col: u16,
     pub size: u16,
     pub text: String,
-    theme: Theme,
+    theme: TileColors,
     state: LetterState,
     ktype: KeyboardType,
 }
 
 impl Keyboard {
-    pub fn new(_x: u16, _y: u16, size: u16, ktype: KeyboardType) -> Self {
+    pub fn new(row: u16, col: u16, size: u16, ktype: KeyboardType) -> Self {
         let text = match ktype {
             KeyboardType::Char(ch) => ch.to_string(),
             KeyboardType::Backspace => "⇦".to_string(),
             KeyboardType::Enter => "Enter".to_string(),
         };
         Self {
-            _x,
-            _y,
+            row,
+            col,
             size,
             text,
-            theme: THEME_X,
+            theme: Theme::dark().key,
             state: LetterState::X,
             ktype,
         }
     }
 
-    pub fn theme(mut self, theme: Theme) -> Self {
+    pub fn theme(mut self, theme: TileColors) -> Self {
         self.theme = theme;
         self
     }
@@ -142,33 +105,51 @@ impl Keyboard {
         }
         None
     }
+
+    /// 点击按键时模拟的物理按键
+    pub fn key_code(&self) -> KeyCode {
+        match self.ktype {
+            KeyboardType::Char(ch) => KeyCode::Char(ch.to_lowercase().next().unwrap_or(ch)),
+            KeyboardType::Backspace => KeyCode::Backspace,
+            KeyboardType::Enter => KeyCode::Enter,
+        }
+    }
 }
 
+/// qwerty布局的屏幕键盘
 pub fn init_keyboard() -> Vec<Vec<Keyboard>> {
-    let mut res = vec![];
-    res.push(
-        ['Q', 'W', 'E', 'R', 'T', 'Y', 'U', 'I', 'O', 'P']
-            .into_iter()
-            .enumerate()
-            .map(|(index, ch)| Keyboard::new(0, index as u16, 3, KeyboardType::Char(ch)))
-            .collect(),
-    );
-    res.push(
-        ['A', 'S', 'D', 'F', 'G', 'H', 'J', 'K', 'L']
-            .into_iter()
-            .enumerate()
-            .map(|(index, ch)| Keyboard::new(1, index as u16, 3, KeyboardType::Char(ch)))
-            .collect(),
-    );
-    let mut last = vec![Keyboard::new(2, 0, 6, KeyboardType::Backspace)];
-    last.extend(
-        ['Z', 'X', 'C', 'V', 'B', 'N', 'M']
-            .into_iter()
-            .enumerate()
-            .map(|(index, ch)| Keyboard::new(2, (index + 1) as u16, 3, KeyboardType::Char(ch))),
-    );
-    last.push(Keyboard::new(2, 8, 6, KeyboardType::Enter));
-    res.push(last);
-
-    res
+    layout_keyboard(&KeyboardLayout::default())
+}
+
+///
+/// 按键盘布局生成屏幕键盘, 删除键和回车键在第三行(不足三行时为最后一行)的两侧
+///
+pub fn layout_keyboard(layout: &KeyboardLayout) -> Vec<Vec<Keyboard>> {
+    let edge_row = layout.rows.len().clamp(1, 3) - 1;
+    layout
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(row, letters)| {
+            let offset = u16::from(row == edge_row);
+            let mut keys = letters
+                .iter()
+                .enumerate()
+                .map(|(index, ch)| {
+                    Keyboard::new(
+                        row as u16,
+                        index as u16 + offset,
+                        3,
+                        KeyboardType::Char(*ch),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if row == edge_row {
+                keys.insert(0, Keyboard::new(row as u16, 0, 6, KeyboardType::Backspace));
+                let col = keys.len() as u16;
+                keys.push(Keyboard::new(row as u16, col, 6, KeyboardType::Enter));
+            }
+            keys
+        })
+        .collect()
 }
@@ -24,6 +24,15 @@ impl Word {
         Ok(Word { letters })
     }
 
+    // 使用给定的字母状态构造, 用于记录外部游戏的反馈
+    pub fn with_states(word: impl AsRef<str>, states: &[LetterState]) -> Result<Word> {
+        let mut word = Self::parse(word)?;
+        for (letter, state) in word.letters.iter_mut().zip(states) {
+            letter.set_state(*state);
+        }
+        Ok(word)
+    }
+
     pub fn set_state(&mut self, index: usize, state: LetterState) {
         let mut letter = self.letters[index];
         letter.set_state(state);
@@ -105,9 +114,9 @@ impl Word {
         }
     }
 }
-impl ToString for Word {
-    fn to_string(&self) -> String {
-        String::from_iter(self.letters.iter().map(|s| s.0))
+impl std::fmt::Display for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&String::from_iter(self.letters.iter().map(|s| s.0)))
     }
 }
 
@@ -1,17 +1,28 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use colored::Colorize;
 use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 use std::io::{BufRead, BufReader};
 
 use crate::command::Opt;
 use crate::error::Result;
+use crate::journal::Journal;
+use crate::layout::KeyboardLayout;
+use crate::pattern::PatternMatrix;
+use crate::record::now_millis;
+use crate::settings::Settings;
 use crate::state::{LetterState, Mode};
 use crate::states::States;
+use crate::t;
 #[cfg(feature = "tui")]
-use crate::tui::ui::UiState;
+use crate::tui::{
+    action::Jobs, animation::Animation, clock::Clock, keymap::Keymap, palette::Palette,
+    theme::Theme, ui::UiState,
+};
 use crate::{state::Letter, word::Word};
 
 // 游戏最大重试次数
@@ -31,6 +42,12 @@ pub struct Wordle {
     pub cached_letter_states: HashSet<Letter>,
     // 历史词汇
     pub history_words: Vec<Word>,
+    // 每次猜测的时间戳(Unix毫秒), 与历史词汇一一对应
+    pub guess_times: Vec<u64>,
+    // 回放中尚未输入的猜测
+    pub replay_queue: VecDeque<String>,
+    // 本局的事件日志
+    pub journal: Journal,
     // 当前游戏的猜测词汇
     pub final_word: Word,
     // 用户输入词库
@@ -49,6 +66,33 @@ pub struct Wordle {
     pub states: States,
     #[cfg(feature = "tui")]
     pub ui_state: UiState,
+    // tui配色主题
+    #[cfg(feature = "tui")]
+    pub theme: Theme,
+    // 正在播放的猜测行动画
+    #[cfg(feature = "tui")]
+    pub animation: Option<Animation>,
+    // 本局用时
+    #[cfg(feature = "tui")]
+    pub clock: Clock,
+    // tui按键绑定
+    #[cfg(feature = "tui")]
+    pub keymap: Keymap,
+    // 打开的命令面板
+    #[cfg(feature = "tui")]
+    pub palette: Option<Palette>,
+    // 在设置中修改, 从下一局开始生效的困难模式
+    #[cfg(feature = "tui")]
+    pub pending_hard: Option<bool>,
+    // 后台计算的结果, 由事件循环取出
+    #[cfg(feature = "tui")]
+    pub jobs: Jobs,
+    // 持久化的用户设置
+    pub settings: Settings,
+    // 屏幕键盘和测试模式键盘状态的字母顺序
+    pub layout: KeyboardLayout,
+    // 求解和赛后分析共用的反馈矩阵
+    pub patterns: Option<Arc<PatternMatrix>>,
     pub game_over: bool,
     pub exit: bool,
 }
@@ -91,6 +135,45 @@ impl Wordle {
             .collect::<Vec<_>>()
     }
 
+    ///
+    /// 键盘上每个已猜过字母的最佳状态(G < Y < R), 按字母排序
+    ///
+    pub fn keyboard_states(&self) -> BTreeMap<char, LetterState> {
+        let mut states = BTreeMap::new();
+        for letter in self.history_words.iter().flat_map(|w| w.get_letters()) {
+            let ch = letter.0.to_ascii_lowercase();
+            let state = states.entry(ch).or_insert(letter.1);
+            if letter.1 < *state {
+                *state = letter.1;
+            }
+        }
+        states
+    }
+
+    ///
+    /// 困难模式下违反规则的提示信息
+    ///
+    pub fn difficult_error_messages(&self) -> Vec<String> {
+        let mut greens = self.get_diffcult_errors_in_green();
+        if !greens.is_empty() {
+            greens.sort();
+            return greens
+                .iter()
+                .map(|(index, letter)| t!("hard.position", index + 1, letter.0))
+                .collect();
+        }
+        let mut yellows = self
+            .get_diffcult_errors_in_yellow()
+            .iter()
+            .map(|(_, letter)| letter.0.to_string())
+            .collect::<Vec<_>>();
+        if yellows.is_empty() {
+            return vec![];
+        }
+        yellows.sort();
+        vec![t!("hard.contain", yellows.join(","))]
+    }
+
     ///
     /// 检查输入的`FINAL`单词是否在final_set中, 如果不在, 则询问是否继续
     ///
@@ -103,7 +186,7 @@ impl Wordle {
             .any(|s| self.states.current_word.to_string().to_lowercase().eq(s))
     }
 
-    fn resolve_difficult(&mut self) -> bool {
+    pub(crate) fn resolve_difficult(&mut self) -> bool {
         // 困难模式下输入的必须包含y和g的字母， 且g的字母位置必须正确
         if self.opt.difficult {
             self.difficult_error_letters.clear();
@@ -169,6 +252,7 @@ impl Wordle {
             .extend(self.states.current_word.get_letters().iter());
 
         self.history_words.push(self.states.current_word.clone());
+        self.guess_times.push(now_millis());
 
         if final_word.eq(&self.states.current_word) {
             return CheckResult::Success;
@@ -188,7 +272,7 @@ impl Wordle {
             .collect();
         if set.is_empty() {
             return Err(crate::error::WordError::CustomError(
-                "input file is empty or invalid".to_string(),
+                t!("error.word_file").to_string(),
             ));
         }
         set.sort();
@@ -196,26 +280,29 @@ impl Wordle {
     }
 
     ///
-    /// 重新设置游戏状态， 当继续开始新的游戏时执行当前操作。
+    /// 清除本局的猜测记录, 不改变答案
     ///
-    pub fn reset(&mut self) -> Result<()> {
+    pub fn clear(&mut self) {
         self.cached_letter_states.clear();
         self.history_words.clear();
+        self.guess_times.clear();
         self.difficult_error_letters.clear();
         self.game_over = false;
         self.states.reset();
+    }
+
+    ///
+    /// 重新设置游戏状态， 当继续开始新的游戏时执行当前操作。
+    ///
+    pub fn reset(&mut self) -> Result<()> {
+        self.clear();
+        self.replay_queue.clear();
         if self.opt.random {
-            let seed = if let Some(seed) = self.opt.seed {
-                seed
-            } else {
-                2048
-            };
+            let seed = self.opt.seed.unwrap_or(2048);
             let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
             let index = rng.gen_range(0..self.final_set.len());
-            let word = Word::parse(self.final_set[index].as_str())?;
-            if self.is_final_word_valid() {
-                self.final_word = word;
-            } else {
+            self.final_word = Word::parse(self.final_set[index].as_str())?;
+            if !self.is_final_word_valid() {
                 self.reset()?;
             }
         }
@@ -223,21 +310,15 @@ impl Wordle {
         #[cfg(feature = "tui")]
         {
             let final_word = if self.opt.random {
-                let seed = if let Some(seed) = self.opt.seed {
-                    seed
-                } else {
-                    2048
-                };
+                let seed = self.opt.seed.unwrap_or(2048);
                 let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
                 let index: usize = rng.gen_range(0..self.final_set.len());
                 Some(self.final_set[index].to_string())
-            } else if let Some(ref word) = self.opt.word {
-                Some(word.to_string())
             } else {
-                None
+                self.opt.word.clone()
             };
             if let Some(final_word) = final_word {
-                self.final_word = Word::parse(final_word).unwrap_or(Word::default());
+                self.final_word = Word::parse(final_word).unwrap_or_default();
                 if !self.is_final_word_valid() {
                     self.reset()?;
                 }
@@ -251,7 +332,7 @@ impl Wordle {
         if self.opt.word.is_none() && !self.opt.random {
             let mut stdin = std::io::stdin().lock();
             loop {
-                println!("{}", "please enter the specified final word: ".blue());
+                println!("{}", t!("cmd.final_word.prompt").blue());
                 let mut w = String::new();
                 stdin.read_line(&mut w)?;
                 let word = Word::parse(&w)?;
@@ -261,7 +342,7 @@ impl Wordle {
                     drop(stdin);
                     break;
                 } else {
-                    eprintln!("{}", "INVALID final word.".red());
+                    eprintln!("{}", t!("cmd.final_word.invalid").red());
                 }
             }
         }
@@ -280,12 +361,58 @@ pub enum CheckResult {
 ///
 /// 游戏统计状态
 ///
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WordleStatistic {
     // 总局数
     pub total: u32,
     // 成功次数
     pub success_total: u32,
+    // 猜中时所用次数的分布, 第i项为第i+1次猜中的局数
+    pub distribution: Vec<u32>,
     // 所有猜测中最频繁使用的5个词和次数
+    #[serde(skip)]
     pub high_frequency_words: Vec<(usize, Word)>,
 }
+
+impl WordleStatistic {
+    ///
+    /// 记录一局的结果, `guesses`为本局的猜测次数
+    ///
+    pub fn record(&mut self, won: bool, guesses: usize) {
+        self.total += 1;
+        if won {
+            self.success_total += 1;
+            if self.distribution.len() < MAX_RETRY_TIMES as usize {
+                self.distribution.resize(MAX_RETRY_TIMES as usize, 0);
+            }
+            if let Some(count) = self.distribution.get_mut(guesses.saturating_sub(1)) {
+                *count += 1;
+            }
+        }
+    }
+
+    /// 胜率, 百分比
+    pub fn win_rate(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.success_total as f64 * 100.0 / self.total as f64
+    }
+
+    ///
+    /// 读取统计, 文件不存在时从零开始
+    ///
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
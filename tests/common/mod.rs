@@ -0,0 +1,51 @@
+//! 集成测试共用的游戏构造和屏幕读取
+#![allow(dead_code)]
+
+use wordle::{
+    buildin_words::{ACCEPTABLE, FINAL},
+    word::Word,
+    wordle::Wordle,
+};
+
+/// 使用内置词库, 还没有答案的游戏
+pub fn builtin_wordle() -> Wordle {
+    Wordle {
+        acceptable_set: ACCEPTABLE.iter().map(|s| s.to_string()).collect(),
+        final_set: FINAL.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+/// 使用内置词库, 答案为`answer`的游戏, 其余字段用`..new_wordle(answer)`覆盖
+pub fn new_wordle(answer: &str) -> Wordle {
+    Wordle {
+        final_word: Word::parse(answer).unwrap(),
+        ..builtin_wordle()
+    }
+}
+
+/// 依次猜测`guesses`, 返回与`answer`比较后带颜色的猜测记录
+pub fn play(guesses: &[&str], answer: &str) -> Vec<Word> {
+    let answer = Word::parse(answer).unwrap();
+    guesses
+        .iter()
+        .map(|guess| {
+            let mut word = Word::parse(guess).unwrap();
+            word.diff(&answer);
+            word
+        })
+        .collect()
+}
+
+/// 测试终端缓冲区中每一行的文字
+#[cfg(feature = "tui")]
+pub fn screen_lines(buffer: &ratatui::buffer::Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            (area.left()..area.right())
+                .map(|x| buffer.get(x, y).symbol.as_str())
+                .collect()
+        })
+        .collect()
+}
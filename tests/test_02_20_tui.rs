@@ -1,43 +1,15 @@
-use std::io;
-
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, KeyCode},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
 use ratatui::{
-    prelude::{Constraint, CrosstermBackend, Layout},
+    backend::TestBackend,
+    prelude::{Constraint, Layout},
     widgets::Paragraph,
     Terminal,
 };
 
-fn init() -> Result<(), io::Error> {
-    enable_raw_mode()?;
-    crossterm::execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
-    Ok(())
-}
-
-fn startup() -> Result<Terminal<CrosstermBackend<io::Stderr>>, io::Error> {
-    let backend = CrosstermBackend::new(io::stderr());
-    let terminal = Terminal::new(backend)?;
-    Ok(terminal)
-}
-
-fn exit(terminal: &mut Terminal<CrosstermBackend<io::Stderr>>) -> Result<(), io::Error> {
-    disable_raw_mode()?;
-    crossterm::execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    Ok(())
-}
-
 #[test]
-fn test_layout() -> Result<(), io::Error> {
-    init()?;
-    let mut terminal = startup()?;
-    loop {
-        terminal.draw(|f| {
+fn test_layout() {
+    let mut terminal = Terminal::new(TestBackend::new(20, 6)).unwrap();
+    terminal
+        .draw(|f| {
             let size = f.size();
             let mut row_constraints = vec![];
             for _ in 0..size.height as usize {
@@ -64,14 +36,20 @@ fn test_layout() -> Result<(), io::Error> {
                     }
                 }
             }
-        })?;
+        })
+        .unwrap();
 
-        if let event::Event::Key(key_event) = event::read()? {
-            if let KeyCode::Esc = key_event.code {
-                break;
-            }
-        }
+    // 偶数行每隔两列一个`A`, 奇数行为空
+    let buffer = terminal.backend().buffer();
+    for y in 0..6 {
+        let line = (0..20)
+            .map(|x| buffer.get(x, y).symbol.as_str())
+            .collect::<String>();
+        let expected = if y % 2 == 0 {
+            "  A   A   A   A   A "
+        } else {
+            "                    "
+        };
+        assert_eq!(line, expected);
     }
-    exit(&mut terminal)?;
-    Ok(())
 }
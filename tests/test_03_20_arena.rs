@@ -0,0 +1,159 @@
+#![cfg(unix)]
+
+use std::{fs, path::Path, time::Duration};
+
+use wordle::{
+    arena::{seeded_answers, Arena, GameOutcome},
+    buildin_words::{ACCEPTABLE, FINAL},
+};
+
+///
+/// 写一个按顺序回复猜测的脚本机器人, 每局从第一个回复开始, 用完后重复最后一个.
+/// 没有回复时从不应答
+///
+fn scripted_bot(dir: &Path, name: &str, replies: &[&str]) -> String {
+    let arms = replies
+        .iter()
+        .enumerate()
+        .map(|(index, reply)| {
+            let pattern = if index + 1 == replies.len() {
+                "*".to_string()
+            } else {
+                (index + 1).to_string()
+            };
+            format!("    {}) echo '{}' ;;\n", pattern, reply)
+        })
+        .collect::<String>();
+    let script = format!(
+        r#"n=0
+while read -r line; do
+  case "$line" in
+    *'"game_over":true'*|*'"event":"result"'*) continue ;;
+    *'"event":"start"'*) n=0 ;;
+  esac
+  n=$((n+1))
+  case $n in
+{}    *) : ;;
+  esac
+done
+"#,
+        arms
+    );
+    let path = dir.join(format!("{}.sh", name));
+    fs::write(&path, script).unwrap();
+    format!("sh {}", path.display())
+}
+
+fn guess(word: &str) -> String {
+    format!(r#"{{"cmd":"guess","word":"{}"}}"#, word)
+}
+
+#[test]
+fn test_seeded_answers() {
+    let final_set = FINAL.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    let answers = seeded_answers(&final_set, 7, 20);
+    assert_eq!(answers.len(), 20);
+    assert!(answers.iter().all(|answer| final_set.contains(answer)));
+    // 相同种子得到相同序列, 所有机器人面对同样的答案
+    assert_eq!(answers, seeded_answers(&final_set, 7, 20));
+    assert_ne!(answers, seeded_answers(&final_set, 8, 20));
+}
+
+#[test]
+fn test_outcomes_and_ranking() {
+    let dir = std::env::temp_dir().join(format!("wordle-arena-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let (crane, cigar) = (guess("crane"), guess("cigar"));
+    let bots = vec![
+        scripted_bot(&dir, "loser", &[&crane]),
+        scripted_bot(&dir, "second", &[&crane, &cigar]),
+        scripted_bot(&dir, "first", &[&cigar]),
+        // 两次无效命令在允许范围内, 第三次猜中
+        scripted_bot(&dir, "sloppy", &["oops", "{}", &cigar]),
+        scripted_bot(&dir, "garbage", &["oops"]),
+        scripted_bot(&dir, "sleeper", &[]),
+    ];
+    let arena = Arena {
+        bots: bots.clone(),
+        answers: vec!["cigar".to_string(); 2],
+        acceptable_set: ACCEPTABLE.iter().map(|s| s.to_string()).collect(),
+        final_set: FINAL.iter().map(|s| s.to_string()).collect(),
+        difficult: false,
+        timeout: Duration::from_millis(300),
+        max_errors: 2,
+    };
+    let reports = arena.run().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let outcomes = |bot: &str| {
+        let report = reports.iter().find(|report| report.name == bot).unwrap();
+        report.outcomes.clone()
+    };
+    assert_eq!(outcomes(&bots[0]), vec![GameOutcome::Lost; 2]);
+    assert_eq!(outcomes(&bots[1]), vec![GameOutcome::Won(2); 2]);
+    assert_eq!(outcomes(&bots[2]), vec![GameOutcome::Won(1); 2]);
+    assert_eq!(outcomes(&bots[3]), vec![GameOutcome::Won(1); 2]);
+    assert_eq!(outcomes(&bots[4]), vec![GameOutcome::Forfeit; 2]);
+    assert_eq!(outcomes(&bots[5]), vec![GameOutcome::Timeout; 2]);
+
+    // 按胜率, 再按平均猜测次数排序
+    let names = reports
+        .iter()
+        .map(|report| report.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(&names[..3], [&bots[2], &bots[3], &bots[1]]);
+    let first = &reports[0];
+    assert_eq!((first.wins, first.worst_case), (2, Some(1)));
+    assert_eq!(first.win_rate, 1.0);
+    let sleeper = reports.iter().find(|r| r.name == bots[5]).unwrap();
+    assert_eq!((sleeper.timeouts, sleeper.worst_case), (2, None));
+}
+
+#[test]
+fn test_bot_exits_between_games() {
+    let dir = std::env::temp_dir().join(format!("wordle-arena-exit-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    // 猜中一局后就退出的机器人
+    let path = dir.join("quitter.sh");
+    fs::write(
+        &path,
+        r#"while read -r line; do
+  case "$line" in
+    *'"event":"result"'*) exit 0 ;;
+    *'"event":"start"'*) echo '{"cmd":"guess","word":"cigar"}' ;;
+  esac
+done
+"#,
+    )
+    .unwrap();
+    let quitter = format!("sh {}", path.display());
+    let arena = Arena {
+        bots: vec![quitter.clone(), "/nonexistent/bot".to_string()],
+        answers: vec!["cigar".to_string(); 4],
+        acceptable_set: ACCEPTABLE.iter().map(|s| s.to_string()).collect(),
+        final_set: FINAL.iter().map(|s| s.to_string()).collect(),
+        difficult: false,
+        timeout: Duration::from_millis(300),
+        max_errors: 2,
+    };
+    let reports = arena.run().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    // 两局之间退出只判负下一局, 之后重新启动继续比赛
+    let quitter = reports.iter().find(|r| r.name == quitter).unwrap();
+    assert_eq!(
+        quitter.outcomes,
+        vec![
+            GameOutcome::Won(1),
+            GameOutcome::Forfeit,
+            GameOutcome::Won(1),
+            GameOutcome::Forfeit
+        ]
+    );
+    // 无法启动的机器人每局判负, 不影响整个比赛
+    let missing = reports
+        .iter()
+        .find(|r| r.name == "/nonexistent/bot")
+        .unwrap();
+    assert_eq!(missing.outcomes, vec![GameOutcome::Forfeit; 4]);
+}
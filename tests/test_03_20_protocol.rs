@@ -0,0 +1,137 @@
+mod common;
+
+use wordle::{
+    command::Opt,
+    protocol::{BotCommand, BotEvent, ErrorKind},
+    state::LetterState,
+    wordle::Wordle,
+};
+
+fn new_wordle(answer: &str, difficult: bool) -> Wordle {
+    Wordle {
+        opt: Opt {
+            word: Some(answer.to_string()),
+            difficult,
+            ..Default::default()
+        },
+        ..common::new_wordle(answer)
+    }
+}
+
+fn guess(word: &str) -> BotCommand {
+    BotCommand::Guess {
+        word: word.to_string(),
+    }
+}
+
+#[test]
+fn test_protocol_session() {
+    let input = [
+        r#"{"cmd":"guess","word":"zzzzz"}"#,
+        r#"{"cmd":"guess","word":"CRANE"}"#,
+        r#"not json"#,
+        r#"{"cmd":"guess","word":"cigar"}"#,
+    ]
+    .join("\n");
+    let mut output = vec![];
+    let mut wordle = new_wordle("cigar", false);
+    wordle.run_protocol(input.as_bytes(), &mut output).unwrap();
+    let events = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<BotEvent>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(events.len(), 6);
+    assert!(matches!(events[0], BotEvent::Start { hard: false, .. }));
+    assert!(matches!(
+        events[1],
+        BotEvent::Error {
+            kind: ErrorKind::InvalidWord,
+            ..
+        }
+    ));
+    match &events[2] {
+        BotEvent::Feedback {
+            attempt,
+            pattern,
+            keyboard,
+            ..
+        } => {
+            assert_eq!(*attempt, 1);
+            assert_eq!(pattern, "GYYRR");
+            assert_eq!(keyboard.get(&'c'), Some(&LetterState::G));
+            assert_eq!(keyboard.get(&'e'), Some(&LetterState::R));
+        }
+        e => panic!("unexpected event {:?}", e),
+    }
+    assert!(matches!(
+        events[3],
+        BotEvent::Error {
+            kind: ErrorKind::BadCommand,
+            ..
+        }
+    ));
+    assert_eq!(
+        events[5],
+        BotEvent::Result {
+            won: true,
+            guesses: 2,
+            answer: "cigar".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_protocol_hard_mode_and_game_over() {
+    let mut wordle = new_wordle("cigar", true);
+    wordle.handle_command(guess("crane")).unwrap();
+    let events = wordle.handle_command(guess("hello")).unwrap();
+    match &events[..] {
+        [BotEvent::Error {
+            kind: ErrorKind::HardMode,
+            message,
+        }] => assert_eq!(message, "1th letter must be c"),
+        e => panic!("unexpected events {:?}", e),
+    }
+
+    for word in ["civic", "cimar", "cigar"] {
+        wordle.handle_command(guess(word)).unwrap();
+    }
+    assert!(wordle.is_game_over());
+    let events = wordle.handle_command(guess("cigar")).unwrap();
+    assert!(matches!(
+        events[..],
+        [BotEvent::Error {
+            kind: ErrorKind::GameOver,
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn test_protocol_random_new_games() {
+    let random_wordle = || Wordle {
+        opt: Opt {
+            random: true,
+            seed: Some(7),
+            ..Default::default()
+        },
+        ..common::builtin_wordle()
+    };
+    let mut answers = vec![];
+    let mut wordle = random_wordle();
+    for _ in 0..2 {
+        let events = wordle.handle_command(BotCommand::New).unwrap();
+        assert!(matches!(events[..], [BotEvent::Start { .. }]));
+        answers.push(wordle.final_word.to_string());
+    }
+    assert_ne!(answers[0], answers[1]);
+
+    // 相同的种子得到相同的答案序列
+    let mut wordle = random_wordle();
+    for answer in &answers {
+        wordle.handle_command(BotCommand::New).unwrap();
+        assert_eq!(&wordle.final_word.to_string(), answer);
+    }
+}
@@ -0,0 +1,99 @@
+use wordle::{
+    buildin_words::FINAL,
+    solver::{feedback, pattern_matrix, Context, StrategyKind},
+    word::Word,
+};
+
+#[test]
+fn test_feedback_matches_diff() {
+    for guess in FINAL.iter().take(40) {
+        for answer in FINAL.iter().skip(100).take(40) {
+            let mut word = Word::parse(guess).unwrap();
+            word.diff(&Word::parse(answer).unwrap());
+            let states = word.get_letters().iter().map(|l| l.1).collect::<Vec<_>>();
+            assert_eq!(feedback(guess, answer).to_vec(), states, "{guess} {answer}");
+        }
+    }
+}
+
+#[test]
+fn test_strategy_names() {
+    for kind in StrategyKind::ALL {
+        assert_eq!(kind.name().parse::<StrategyKind>().unwrap(), kind);
+        assert_eq!(kind.build(0).name(), kind.name());
+    }
+    assert!("unknown".parse::<StrategyKind>().is_err());
+}
+
+#[test]
+fn test_strategies_solve() {
+    let words = FINAL
+        .iter()
+        .take(60)
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    for kind in StrategyKind::ALL {
+        let strategy = kind.build(7);
+        for answer in words.iter().take(20) {
+            let answer_word = Word::parse(answer).unwrap();
+            let mut history = vec![];
+            loop {
+                let context = Context {
+                    history: &history,
+                    acceptable_set: &words,
+                    final_set: &words,
+                    difficult: true,
+                    patterns: None,
+                };
+                let guess = strategy.next_guess(&context).unwrap();
+                let mut word = Word::parse(&guess).unwrap();
+                word.diff(&answer_word);
+                history.push(word);
+                if guess == *answer {
+                    break;
+                }
+                assert!(history.len() < 10, "{} failed on {}", kind.name(), answer);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_patterns_match_scoring() {
+    let words = FINAL
+        .iter()
+        .take(200)
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    let matrix = pattern_matrix(&words[..150], &words[50..], None).unwrap();
+    let mut history = vec![Word::parse("cigar").unwrap()];
+    history[0].diff(&Word::parse(&words[120]).unwrap());
+    let context = Context {
+        history: &history,
+        acceptable_set: &words[..150],
+        final_set: &words[50..],
+        difficult: false,
+        patterns: None,
+    };
+    let with_patterns = Context {
+        patterns: Some(&matrix),
+        ..context
+    };
+    let candidates = context.candidates();
+    let (plain, lookup) = (
+        context.partitioner(&candidates),
+        with_patterns.partitioner(&candidates),
+    );
+    for guess in context.guesses() {
+        assert_eq!(plain.partition(guess), lookup.partition(guess), "{guess}");
+    }
+    // 矩阵中没有的猜测逐个计算
+    assert_eq!(plain.partition("zzzzz"), lookup.partition("zzzzz"));
+    for kind in [StrategyKind::Entropy, StrategyKind::Minimax] {
+        let strategy = kind.build(0);
+        assert_eq!(
+            strategy.suggestions(&context, 5),
+            strategy.suggestions(&with_patterns, 5)
+        );
+    }
+}
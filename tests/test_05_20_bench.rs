@@ -0,0 +1,31 @@
+use wordle::{
+    bench::{run, sample_answers},
+    buildin_words::FINAL,
+    solver::StrategyKind,
+};
+
+#[test]
+fn test_bench_report() {
+    let words = FINAL
+        .iter()
+        .take(80)
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    let answers = sample_answers(&words, 42, 30);
+    assert_eq!(answers, sample_answers(&words, 42, 30));
+    assert_eq!(answers.len(), 30);
+
+    let strategy = StrategyKind::Frequency.build(42);
+    let report = run(strategy.as_ref(), &words, &words, None, &answers, true, 5);
+    assert_eq!(report.strategy, "frequency");
+    assert_eq!(report.games, 30);
+    assert_eq!(report.slowest.len(), 5);
+    let solved = report.distribution.values().sum::<usize>();
+    let gave_up = report
+        .failures
+        .iter()
+        .filter(|r| r.guesses.is_none())
+        .count();
+    assert_eq!(solved + gave_up, 30);
+    assert!(report.average_guesses >= 1.0);
+}
@@ -0,0 +1,75 @@
+use wordle::{
+    buildin_words::{ACCEPTABLE, FINAL},
+    pattern::{score, Pattern, PatternMatrix},
+    state::LetterState,
+    word::Word,
+};
+
+#[test]
+fn test_score_matches_diff() {
+    let pairs = [("speed", "abide"), ("eerie", "there"), ("llama", "hello")];
+    let more = ACCEPTABLE.iter().step_by(97).flat_map(|guess| {
+        FINAL
+            .iter()
+            .step_by(53)
+            .map(move |answer| (*guess, *answer))
+    });
+    for (guess, answer) in pairs.into_iter().chain(more) {
+        let mut word = Word::parse(guess).unwrap();
+        word.diff(&Word::parse(answer).unwrap());
+        let states = word.get_letters().iter().map(|l| l.1).collect::<Vec<_>>();
+        let pattern = score(guess, answer);
+        assert_eq!(pattern, Pattern::from_states(&states), "{guess} {answer}");
+        assert_eq!(pattern.states().to_vec(), states);
+    }
+}
+
+#[test]
+fn test_pattern_encoding() {
+    let pattern = score("speed", "abide");
+    assert_eq!(pattern.to_string(), "RRYRY");
+    assert!(score("cigar", "cigar").is_solved());
+    assert_eq!(
+        Pattern::from_states(&[LetterState::G; 5]),
+        Pattern::ALL_GREEN
+    );
+    assert_eq!(Pattern::from_states(&[LetterState::R; 5]), Pattern(0));
+}
+
+#[test]
+fn test_score_ignores_case() {
+    assert!(score("CRANE", "crane").is_solved());
+    assert_eq!(score("Speed", "ABIDE"), score("speed", "abide"));
+    // 非字母不会被当作某个字母
+    assert_eq!(score("[[[[[", "abcde"), Pattern(0));
+    assert_eq!(score("a1b2c", "c2b1a").to_string(), "YRGRY");
+}
+
+#[test]
+fn test_matrix_cache() {
+    let guesses = ACCEPTABLE
+        .iter()
+        .take(50)
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    let answers = FINAL
+        .iter()
+        .take(30)
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    let dir = std::env::temp_dir().join(format!("wordle-pattern-{}", std::process::id()));
+    let built = PatternMatrix::load_or_build(&dir, &guesses, &answers).unwrap();
+    assert_eq!(built.get(3, 7), score(&guesses[3], &answers[7]));
+    assert_eq!(built.row(3)[7], built.get(3, 7).0);
+    assert_eq!(built.guess_index(&guesses[3]), Some(3));
+    assert_eq!(built.answer_index(&answers[7]), Some(7));
+    assert_eq!(built.answer_index("zzzzz"), None);
+
+    let path = PatternMatrix::cache_path(&dir, &guesses, &answers);
+    let loaded = PatternMatrix::load(&path, &guesses, &answers).unwrap();
+    for g in 0..guesses.len() {
+        assert_eq!(loaded.row(g), built.row(g));
+    }
+    assert!(PatternMatrix::load(&path, &answers, &guesses).is_err());
+    std::fs::remove_dir_all(dir).unwrap();
+}
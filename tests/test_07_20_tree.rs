@@ -0,0 +1,51 @@
+use wordle::{
+    buildin_words::{ACCEPTABLE, FINAL},
+    pattern::{score, PatternMatrix},
+    tree::{DecisionTree, TreeBuilder},
+};
+
+#[test]
+fn test_tree_solves_every_answer() {
+    let answers = FINAL
+        .iter()
+        .take(120)
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    let mut guesses = ACCEPTABLE
+        .iter()
+        .take(300)
+        .map(|s| s.to_string())
+        .chain(answers.iter().cloned())
+        .collect::<Vec<_>>();
+    guesses.sort();
+    guesses.dedup();
+    let matrix = PatternMatrix::build(&guesses, &answers);
+    let tree = TreeBuilder::new(&matrix, 3)
+        .unwrap()
+        .build("cigar")
+        .unwrap();
+    assert_eq!(tree.guess, "cigar");
+    assert_eq!(tree.size, answers.len());
+    assert!(tree.within_limit());
+
+    let mut total = 0;
+    for answer in answers.iter() {
+        let mut node: &DecisionTree = &tree;
+        let mut guesses = 1;
+        loop {
+            let pattern = score(&node.guess, answer);
+            if pattern.is_solved() {
+                break;
+            }
+            node = &node.children[&pattern.to_string()];
+            guesses += 1;
+        }
+        assert!(guesses <= tree.depth);
+        total += guesses;
+    }
+    assert_eq!(total, tree.total_guesses);
+
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(serde_json::from_str::<DecisionTree>(&json).unwrap(), tree);
+    assert!(tree.to_text().starts_with("cigar (120 answers"));
+}
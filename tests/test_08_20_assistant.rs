@@ -0,0 +1,51 @@
+mod common;
+
+use wordle::{
+    assistant::parse_feedback_line,
+    pattern::{score, Pattern},
+    state::LetterState,
+};
+
+#[test]
+fn test_parse_feedback() {
+    let (guess, pattern) = parse_feedback_line("CRANE GYXXR").unwrap();
+    assert_eq!(guess, "crane");
+    assert_eq!(
+        pattern.states(),
+        [
+            LetterState::G,
+            LetterState::Y,
+            LetterState::R,
+            LetterState::R,
+            LetterState::R
+        ]
+    );
+    assert_eq!(
+        "🟩🟨⬛⬜🟩".parse::<Pattern>().unwrap().to_string(),
+        "GYRRG"
+    );
+    assert!(parse_feedback_line("crane").is_err());
+    assert!(parse_feedback_line("crane GYXX").is_err());
+    assert!(parse_feedback_line("crane GYXXZ").is_err());
+}
+
+#[test]
+fn test_record_feedback() {
+    let mut wordle = common::builtin_wordle();
+    let answer = "cigar";
+    wordle
+        .record_feedback("crane", score("crane", answer))
+        .unwrap();
+    assert!(wordle.record_feedback("zzzzz", Pattern(0)).is_err());
+    let candidates = wordle.remaining_candidates();
+    assert!(candidates.contains(&answer.to_string()));
+    assert!(candidates
+        .iter()
+        .all(|c| score("crane", c) == score("crane", answer)));
+    assert_eq!(wordle.history_words.len(), 1);
+    assert_eq!(wordle.states.current_try_times, 1);
+
+    wordle.record_feedback("cigar", Pattern::ALL_GREEN).unwrap();
+    assert!(wordle.is_game_over());
+    assert!(wordle.record_feedback("cigar", Pattern::ALL_GREEN).is_err());
+}
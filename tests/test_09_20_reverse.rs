@@ -0,0 +1,53 @@
+mod common;
+
+use wordle::{
+    pattern::{score, Pattern},
+    wordle::MAX_RETRY_TIMES,
+};
+
+#[test]
+fn test_reverse_guesses_secret() {
+    let mut wordle = common::builtin_wordle();
+    let secret = "shire";
+    while !wordle.is_game_over() {
+        let guess = wordle.reverse_guess().unwrap();
+        wordle
+            .reverse_feedback(&guess, score(&guess, secret))
+            .unwrap();
+    }
+    assert!(wordle.reverse_solved());
+    assert!(wordle.history_words.len() <= MAX_RETRY_TIMES as usize);
+    assert_eq!(wordle.history_words.last().unwrap().to_string(), secret);
+}
+
+#[test]
+fn test_reverse_inconsistent_feedback() {
+    let mut wordle = common::builtin_wordle();
+    wordle
+        .reverse_feedback("crane", score("crane", "cigar"))
+        .unwrap();
+    let before = wordle.remaining_candidates();
+    // `c`已经是绿色, 再声称全部为灰色与之前的反馈矛盾
+    assert!(wordle.reverse_feedback("cloth", Pattern(0)).is_err());
+    assert!(!wordle.is_game_over());
+    assert_eq!(wordle.history_words.len(), 1);
+    assert_eq!(wordle.states.current_try_times, 1);
+    assert_eq!(wordle.remaining_candidates(), before);
+}
+
+#[test]
+fn test_reverse_word_not_in_final_set() {
+    let mut wordle = common::builtin_wordle();
+    // zymic 可以猜测, 但不在答案词库中: 反馈没有矛盾, 照常记录, 之后没有可以猜的答案
+    let secret = "zymic";
+    assert!(!wordle.final_set.contains(&secret.to_string()));
+    let mut remaining = usize::MAX;
+    while let Some(guess) = wordle.reverse_guess() {
+        remaining = wordle
+            .reverse_feedback(&guess, score(&guess, secret))
+            .unwrap();
+    }
+    assert_eq!(remaining, 0);
+    assert!(!wordle.reverse_solved());
+    assert!(wordle.history_words.len() < MAX_RETRY_TIMES as usize);
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use common::play;
+use wordle::{analysis::analyze, buildin_words::FINAL};
+
+#[test]
+fn test_analyze_game() {
+    let words = FINAL
+        .iter()
+        .take(200)
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>();
+    let answer = words[150].clone();
+    let history = play(&[&words[0], &words[1], &answer], &answer);
+    let analysis = analyze(&history, &words, &words, false, None);
+
+    assert!(analysis.solved);
+    assert_eq!(analysis.guesses.len(), 3);
+    let first = &analysis.guesses[0];
+    assert_eq!(first.candidates_before, words.len());
+    assert!(first.best_info >= first.expected_info);
+    assert!(first.skill <= 100);
+    for pair in analysis.guesses.windows(2) {
+        assert_eq!(pair[0].candidates_after, pair[1].candidates_before);
+    }
+    let last = analysis.guesses.last().unwrap();
+    assert_eq!(last.pattern, "GGGGG");
+    assert_eq!(last.candidates_after, 1);
+    let luck = analysis.guesses.iter().map(|g| g.luck).sum::<f64>();
+    assert!((analysis.luck - luck).abs() < 1e-9);
+}
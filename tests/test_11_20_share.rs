@@ -0,0 +1,28 @@
+mod common;
+
+use common::play;
+use wordle::share::{osc52, share_text};
+
+#[test]
+fn test_share_text() {
+    let history = play(&["crane", "shire"], "shire");
+    assert_eq!(
+        share_text(Some(42), &history, false, false),
+        "Wordle 42 2/6\n\n⬛🟨⬛⬛🟩\n🟩🟩🟩🟩🟩"
+    );
+    assert_eq!(
+        share_text(Some(42), &history, true, true),
+        "Wordle 42 2/6*\n\n⬛🟦⬛⬛🟧\n🟧🟧🟧🟧🟧"
+    );
+    let history = play(&["crane", "cigar"], "shire");
+    assert!(share_text(Some(7), &history, false, false).starts_with("Wordle 7 X/6\n"));
+    // 指定答案的对局没有局号
+    assert!(share_text(None, &history, false, false).starts_with("Wordle X/6\n"));
+}
+
+#[test]
+fn test_osc52() {
+    assert_eq!(osc52("hi"), "\x1b]52;c;aGk=\x07");
+    assert_eq!(osc52("abc"), "\x1b]52;c;YWJj\x07");
+    assert_eq!(osc52("Wordle"), "\x1b]52;c;V29yZGxl\x07");
+}
@@ -0,0 +1,39 @@
+use wordle::{buildin_words::FINAL, challenge::Challenge, command::Opt, wordle::MAX_RETRY_TIMES};
+
+#[test]
+fn test_challenge_round_trip() {
+    let final_set = FINAL.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    for (word, difficult) in [("shire", true), ("cigar", false)] {
+        let challenge = Challenge::new(word, difficult);
+        let code = challenge.encode();
+        assert!(!code.to_lowercase().contains(word));
+        let decoded = code.to_lowercase().parse::<Challenge>().unwrap();
+        assert_eq!(decoded, challenge);
+        assert_eq!(decoded.max_guesses, MAX_RETRY_TIMES);
+        decoded.validate(&final_set).unwrap();
+    }
+    assert_ne!(
+        Challenge::new("shire", false).encode(),
+        Challenge::new("shire", true).encode()
+    );
+
+    let mut opt = Opt::default();
+    Challenge::new("shire", true)
+        .apply(&mut opt, &final_set)
+        .unwrap();
+    assert_eq!(opt.word.as_deref(), Some("shire"));
+    assert!(opt.difficult);
+}
+
+#[test]
+fn test_challenge_rejects_invalid() {
+    let final_set = vec!["cigar".to_string()];
+    assert!("ABCD-EFGH".parse::<Challenge>().is_err());
+    assert!("not a code!".parse::<Challenge>().is_err());
+    let code = Challenge::new("cigar", false).encode();
+    // 修改一个字符后校验和不匹配
+    let replaced = if code.as_bytes()[5] == b'0' { "1" } else { "0" };
+    let code = format!("{}{}{}", &code[..5], replaced, &code[6..]);
+    assert!(code.parse::<Challenge>().is_err());
+    assert!(Challenge::new("shire", false).validate(&final_set).is_err());
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use wordle::{pattern::Pattern, record::GameRecord, wordle::Wordle};
+
+/// 猜测过`guesses`的游戏, 每次猜测间隔一秒
+fn played(guesses: &[&str], answer: &str) -> Wordle {
+    let history_words = common::play(guesses, answer);
+    Wordle {
+        guess_times: (1..=history_words.len() as u64).map(|i| i * 1000).collect(),
+        history_words,
+        ..common::new_wordle(answer)
+    }
+}
+
+#[test]
+fn test_record_round_trip() {
+    let wordle = played(&["crane", "shire"], "shire");
+    let record = wordle.record();
+    assert_eq!(record.answer, "shire");
+    assert_eq!(record.guesses.len(), 2);
+    assert!(record.solved());
+    assert_eq!(record.result(), "2/6");
+    record.verify().unwrap();
+
+    let text = record.to_string();
+    assert!(text.contains("[Answer \"shire\"]"));
+    assert!(text.contains("[Result \"2/6\"]"));
+    assert!(text.contains("1. crane RYRRG "));
+    assert_eq!(text.parse::<GameRecord>().unwrap(), record);
+}
+
+#[test]
+fn test_record_verify_detects_tampering() {
+    let mut record = played(&["crane", "cigar"], "shire").record();
+    assert_eq!(record.result(), "X/6");
+    record.verify().unwrap();
+    record.guesses[1].pattern = Pattern::ALL_GREEN;
+    assert!(record.verify().is_err());
+
+    // 引擎不支持的规则
+    let mut record = played(&["crane"], "shire").record();
+    record.word_length = 6;
+    let error = record.verify().unwrap_err().to_string();
+    assert!(
+        error.contains("6-letter words are not supported"),
+        "{}",
+        error
+    );
+    record.word_length = 5;
+    record.max_guesses = 8;
+    assert!(record.verify().is_err());
+
+    assert!("1. crane RYRRG 0".parse::<GameRecord>().is_err());
+    assert!("[Answer \"shire\"]\n2. crane RYRRG 0"
+        .parse::<GameRecord>()
+        .is_err());
+}
+
+#[test]
+fn test_record_save_and_load() {
+    let dir = std::env::temp_dir().join(format!("wordle-record-{}", std::process::id()));
+    let record = played(&["crane", "shire"], "shire").record();
+    let path = record.save(&dir).unwrap();
+    assert_eq!(GameRecord::load(&path).unwrap(), record);
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use wordle::{
+    command::Opt,
+    journal::{GameEvent, Journal},
+    word::Word,
+    wordle::{CheckResult, Wordle},
+};
+
+fn new_wordle(opt: Opt) -> Wordle {
+    Wordle {
+        opt,
+        ..common::new_wordle("shire")
+    }
+}
+
+fn guess(wordle: &mut Wordle, word: &str) -> CheckResult {
+    wordle.type_word(word).unwrap();
+    wordle.submit_guess().unwrap()
+}
+
+#[test]
+fn test_state_derived_from_events() {
+    let mut wordle = new_wordle(Opt::default());
+    wordle.start_game().unwrap();
+    assert_eq!(guess(&mut wordle, "zzzzz"), CheckResult::InValid);
+    assert_eq!(guess(&mut wordle, "crane"), CheckResult::Wrong);
+    assert_eq!(guess(&mut wordle, "shire"), CheckResult::Success);
+    assert!(wordle.is_game_over());
+    assert!(!wordle.journal.in_progress());
+    assert!(matches!(
+        wordle.journal.events.last(),
+        Some(GameEvent::Ended { won: true, .. })
+    ));
+
+    let mut derived = new_wordle(Opt::default());
+    derived.restore(&wordle.journal.events).unwrap();
+    assert_eq!(derived.history_words, wordle.history_words);
+    assert_eq!(derived.guess_times, wordle.guess_times);
+    assert_eq!(
+        derived.states.current_try_times,
+        wordle.states.current_try_times
+    );
+    assert!(derived.is_game_over());
+}
+
+#[test]
+fn test_undo_in_practice_mode() {
+    let mut wordle = new_wordle(Opt::default());
+    wordle.start_game().unwrap();
+    guess(&mut wordle, "crane");
+    assert!(wordle.undo().is_err());
+
+    let mut wordle = new_wordle(Opt {
+        practice: true,
+        ..Default::default()
+    });
+    wordle.start_game().unwrap();
+    guess(&mut wordle, "crane");
+    guess(&mut wordle, "shire");
+    assert!(wordle.is_game_over());
+    wordle.undo().unwrap();
+    assert!(!wordle.is_game_over());
+    assert_eq!(wordle.history_words.len(), 1);
+    assert_eq!(wordle.states.current_try_times, 1);
+    wordle.undo().unwrap();
+    assert_eq!(wordle.states.current_try_times, 0);
+    assert!(wordle.undo().is_err());
+    // 撤销也记录在日志中
+    assert!(wordle
+        .journal
+        .events
+        .iter()
+        .any(|e| matches!(e, GameEvent::Undone { .. })));
+}
+
+#[test]
+fn test_autosave_resume() {
+    let path = std::env::temp_dir().join(format!("wordle-journal-{}.jsonl", std::process::id()));
+    let opt = || Opt {
+        autosave: Some(path.clone()),
+        ..Default::default()
+    };
+    let mut wordle = new_wordle(opt());
+    assert!(!wordle.resume_autosave().unwrap());
+    wordle.start_game().unwrap();
+    guess(&mut wordle, "crane");
+    wordle.type_letter('s').unwrap();
+    let (history, events) = (wordle.history_words.clone(), wordle.journal.events.clone());
+    // 模拟崩溃: 缓冲区中输入的字母没有写入, 文件末尾是写了一半的事件
+    std::mem::forget(wordle);
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    write!(file, "{{\"event\":\"letter_ty").unwrap();
+    assert_eq!(Journal::load(&path).unwrap(), events[..events.len() - 1]);
+
+    let mut resumed = new_wordle(opt());
+    resumed.final_word = Word::default();
+    assert!(resumed.resume_autosave().unwrap());
+    assert_eq!(resumed.final_word.to_string(), "shire");
+    assert_eq!(resumed.history_words, history);
+    assert!(resumed.states.current_word.is_empty());
+    assert!(resumed.journal.in_progress());
+    resumed.type_letter('h').unwrap();
+    // 正常退出时写入缓冲区中的事件
+    let events = resumed.journal.events.clone();
+    drop(resumed);
+    assert_eq!(Journal::load(&path).unwrap(), events);
+    std::fs::remove_file(path).unwrap();
+}
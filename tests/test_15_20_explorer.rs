@@ -0,0 +1,73 @@
+mod common;
+
+use wordle::{command::Opt, wordle::Wordle};
+
+fn new_wordle() -> Wordle {
+    let mut wordle = Wordle {
+        opt: Opt {
+            practice: true,
+            ..Default::default()
+        },
+        ..common::new_wordle("shire")
+    };
+    wordle.start_game().unwrap();
+    wordle
+}
+
+fn guess(wordle: &mut Wordle, word: &str) {
+    wordle.type_word(word).unwrap();
+    wordle.submit_guess().unwrap();
+}
+
+fn paths(wordle: &Wordle) -> Vec<String> {
+    wordle
+        .branch_tree()
+        .iter()
+        .map(|node| node.path.join(" "))
+        .collect()
+}
+
+#[test]
+fn test_branch_tree() {
+    let mut wordle = new_wordle();
+    guess(&mut wordle, "crane");
+    guess(&mut wordle, "moist");
+    wordle.rewind(1).unwrap();
+    guess(&mut wordle, "tried");
+    wordle.rewind(0).unwrap();
+    guess(&mut wordle, "slate");
+
+    assert_eq!(
+        paths(&wordle),
+        vec!["crane", "crane moist", "crane tried", "slate"]
+    );
+    let tree = wordle.branch_tree();
+    let active: Vec<bool> = tree.iter().map(|node| node.active).collect();
+    assert_eq!(active, vec![false, false, false, true]);
+    // 猜测越多候选越少
+    assert!(tree[1].candidates < tree[0].candidates);
+    assert!(tree[0].candidates < wordle.final_set.len());
+    assert_eq!(tree[1].depth(), 2);
+
+    // 重复提交相同的猜测不会新增节点
+    wordle.rewind(0).unwrap();
+    guess(&mut wordle, "crane");
+    assert_eq!(paths(&wordle).len(), 4);
+}
+
+#[test]
+fn test_switch_branch() {
+    let mut wordle = new_wordle();
+    guess(&mut wordle, "crane");
+    guess(&mut wordle, "moist");
+    wordle.rewind(0).unwrap();
+    guess(&mut wordle, "slate");
+
+    let target = wordle.branch_tree()[1].path.clone();
+    wordle.switch_branch(&target).unwrap();
+    let history: Vec<String> = wordle.history_words.iter().map(|w| w.to_string()).collect();
+    assert_eq!(history, target);
+    assert_eq!(wordle.states.current_try_times, 2);
+    assert!(wordle.branch_tree()[1].active);
+    assert_eq!(paths(&wordle).len(), 3);
+}
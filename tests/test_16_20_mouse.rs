@@ -0,0 +1,112 @@
+#![cfg(feature = "tui")]
+
+use crossterm::event::KeyCode;
+use ratatui::{backend::TestBackend, buffer::Buffer, prelude::Rect, Terminal};
+use wordle::{
+    i18n::{self, Locale},
+    tui::{
+        ui::{self, MainState, UiState},
+        widgets::init_keyboard,
+    },
+    word::Word,
+    wordle::Wordle,
+};
+
+fn render(wordle: &Wordle, width: u16, height: u16) -> Buffer {
+    // 按中文界面查找按钮
+    i18n::set_locale(Locale::Zh);
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    let keyboards = init_keyboard();
+    terminal
+        .draw(|frame| ui::ui(wordle, frame, &keyboards))
+        .unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// 查找屏幕上`text`出现的所有位置, 跳过中文字符占用的第二列
+fn find(buffer: &Buffer, text: &str) -> Vec<(u16, u16)> {
+    let area = buffer.area;
+    let mut found = vec![];
+    for y in area.top()..area.bottom() {
+        let mut columns = vec![];
+        let mut line = String::new();
+        let mut wide = false;
+        for x in area.left()..area.right() {
+            if std::mem::take(&mut wide) {
+                continue;
+            }
+            let symbol = &buffer.get(x, y).symbol;
+            wide = symbol
+                .chars()
+                .any(|ch| ('\u{2E80}'..='\u{9FFF}').contains(&ch));
+            columns.push(x);
+            line.push_str(symbol);
+        }
+        for (index, _) in line.match_indices(text) {
+            found.push((columns[line[..index].chars().count()], y));
+        }
+    }
+    assert!(!found.is_empty(), "`{}` not found on screen", text);
+    found
+}
+
+/// 点击`text`在屏幕上最后一次出现的位置
+fn click(wordle: &Wordle, width: u16, height: u16, text: &str) -> Option<KeyCode> {
+    let (x, y) = *find(&render(wordle, width, height), text).last().unwrap();
+    ui::hit_test(
+        wordle,
+        Rect::new(0, 0, width, height),
+        &init_keyboard(),
+        x,
+        y,
+    )
+    .map(|key| key.code)
+}
+
+#[test]
+fn test_click_keyboard_follows_resize() {
+    let wordle = Wordle {
+        final_word: Word::parse("shire").unwrap(),
+        ui_state: UiState::Main(MainState::Main),
+        ..Default::default()
+    };
+    for (width, height) in [(100, 40), (60, 34)] {
+        assert_eq!(
+            click(&wordle, width, height, " Q "),
+            Some(KeyCode::Char('q'))
+        );
+        assert_eq!(
+            click(&wordle, width, height, " M "),
+            Some(KeyCode::Char('m'))
+        );
+        assert_eq!(click(&wordle, width, height, "⇦"), Some(KeyCode::Backspace));
+        assert_eq!(click(&wordle, width, height, "Enter"), Some(KeyCode::Enter));
+    }
+    // 键盘区之外没有按键
+    let keyboards = init_keyboard();
+    assert_eq!(
+        ui::hit_test(&wordle, Rect::new(0, 0, 100, 40), &keyboards, 0, 0),
+        None
+    );
+}
+
+#[test]
+fn test_click_popup_buttons() {
+    let mut wordle = Wordle {
+        final_word: Word::parse("shire").unwrap(),
+        ui_state: UiState::Main(MainState::GameOver),
+        ..Default::default()
+    };
+    assert_eq!(click(&wordle, 120, 40, "新游戏"), Some(KeyCode::Enter));
+    assert_eq!(click(&wordle, 120, 40, "复盘"), Some(KeyCode::Char('a')));
+    assert_eq!(click(&wordle, 120, 40, "退出"), Some(KeyCode::Esc));
+    // 弹窗打开时不响应被遮挡的键盘
+    let (x, y) = find(&render(&wordle, 120, 40), "⇦")[0];
+    assert_eq!(
+        ui::hit_test(&wordle, Rect::new(0, 0, 120, 40), &init_keyboard(), x, y),
+        None
+    );
+
+    wordle.ui_state = UiState::Main(MainState::Difficult);
+    assert_eq!(click(&wordle, 120, 40, "继续"), Some(KeyCode::Enter));
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "tui")]
+
+use std::fs;
+
+use ratatui::style::{Color, Modifier};
+use wordle::{
+    state::LetterState,
+    tui::theme::{Theme, BUILTIN_THEMES},
+};
+
+#[test]
+fn test_load_theme_file() {
+    let dir = std::env::temp_dir().join(format!("wordle-theme-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("ocean.json");
+    fs::write(
+        &path,
+        r##"{"correct": {"fg": "black", "bg": "#00aaff"}, "border": "blue"}"##,
+    )
+    .unwrap();
+    let theme = Theme::load(&path).unwrap();
+    assert_eq!(theme.name, "ocean");
+    assert_eq!(theme.correct.bg, Color::Rgb(0, 170, 255));
+    assert_eq!(theme.border, Color::Blue);
+    // 未给出的颜色沿用dark主题
+    assert_eq!(theme.present, Theme::dark().present);
+
+    fs::write(&path, r#"{"border": "no-such-color"}"#).unwrap();
+    assert!(Theme::load(&path).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_builtin_themes_and_no_color() {
+    std::env::remove_var("NO_COLOR");
+    for name in BUILTIN_THEMES {
+        assert_eq!(Theme::resolve(Some(name)).unwrap().name, name);
+    }
+    let blind = Theme::color_blind();
+    assert_ne!(
+        blind.tile(&LetterState::G).bg,
+        blind.tile(&LetterState::Y).bg
+    );
+    // 运行时依次切换, 最后回到第一个
+    let mut theme = Theme::dark();
+    for name in BUILTIN_THEMES.iter().skip(1) {
+        theme = theme.next();
+        assert_eq!(theme.name, *name);
+    }
+    assert_eq!(theme.next().name, "dark");
+
+    std::env::set_var("NO_COLOR", "1");
+    let theme = Theme::resolve(Some("light")).unwrap();
+    assert_eq!(theme.name, "mono");
+    assert_eq!(theme.next().name, "mono");
+    assert!(theme
+        .tile_style(&LetterState::G)
+        .add_modifier
+        .contains(Modifier::REVERSED));
+    std::env::remove_var("NO_COLOR");
+}
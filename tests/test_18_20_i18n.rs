@@ -0,0 +1,105 @@
+use std::{fs, path::Path, sync::Mutex};
+
+use wordle::{
+    error::WordError,
+    i18n::{self, fill, tr, Locale},
+    t,
+};
+
+// 语言是全局设置, 切换语言的测试需要串行执行
+static LOCALE: Mutex<()> = Mutex::new(());
+
+/// 收集源码中`t!("...")`和`tr("...")`用到的键
+fn collect_keys(dir: &Path, keys: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_keys(&path, keys);
+            continue;
+        }
+        if path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&path).unwrap();
+        for call in ["t!(", "tr("] {
+            for (index, _) in source.match_indices(call) {
+                // 跳过`print!(`, `str(`等以相同字符结尾的调用
+                let before = source[..index].chars().next_back();
+                if before.is_some_and(|ch| ch.is_alphanumeric() || ch == '_') {
+                    continue;
+                }
+                let Some(rest) = source[index + call.len()..].trim_start().strip_prefix('"') else {
+                    continue;
+                };
+                let key = &rest[..rest.find('"').unwrap()];
+                if !keys.iter().any(|k| k == key) {
+                    keys.push(key.to_string());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_locale() {
+    assert_eq!("en".parse::<Locale>().unwrap(), Locale::En);
+    assert_eq!("en_US.UTF-8".parse::<Locale>().unwrap(), Locale::En);
+    assert_eq!("zh".parse::<Locale>().unwrap(), Locale::Zh);
+    assert_eq!("zh-CN".parse::<Locale>().unwrap(), Locale::Zh);
+    assert_eq!("zh_CN.UTF-8".parse::<Locale>().unwrap(), Locale::Zh);
+    assert!("C".parse::<Locale>().is_err());
+    assert!("fr_FR".parse::<Locale>().is_err());
+}
+
+#[test]
+fn test_fill() {
+    assert_eq!(fill("{} of {}", &[&1, &"six"]), "1 of six");
+    // 参数不足时占位符替换为空
+    assert_eq!(fill("a{}b{}", &[&1]), "a1b");
+}
+
+#[test]
+fn test_messages_follow_locale() {
+    let _guard = LOCALE.lock().unwrap();
+    let error = WordError::InValidWord("abcde".to_string());
+
+    i18n::set_locale(Locale::Zh);
+    assert_eq!(error.to_string(), "单词 `abcde` 不在可接受的词库中");
+    assert_eq!(t!("ui.main.wrong", 2), "第 2 次猜测错误, 请继续!");
+
+    i18n::set_locale(Locale::En);
+    assert_eq!(
+        error.to_string(),
+        "your word `abcde` is not in the acceptable word list"
+    );
+    assert_eq!(t!("ui.main.title"), "Wordle");
+    // 目录中没有的键原样返回
+    assert_eq!(t!("no.such.key"), "no.such.key");
+}
+
+#[test]
+fn test_catalog_covers_source() {
+    let _guard = LOCALE.lock().unwrap();
+    let mut keys = vec![];
+    collect_keys(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+        &mut keys,
+    );
+    assert!(keys.iter().any(|key| key == "cmd.final_word.prompt"));
+    assert!(keys.iter().any(|key| key == "error.record_line"));
+
+    for locale in [Locale::En, Locale::Zh] {
+        i18n::set_locale(locale);
+        for key in &keys {
+            let key: &'static str = Box::leak(key.clone().into_boxed_str());
+            let message = tr(key);
+            assert!(
+                message != key && !message.is_empty(),
+                "`{}` is missing from the {:?} catalog",
+                key,
+                locale
+            );
+        }
+    }
+    i18n::set_locale(Locale::En);
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use std::fs;
+
+use wordle::{settings::Settings, wordle::WordleStatistic};
+
+#[test]
+fn test_settings_round_trip() {
+    let dir = std::env::temp_dir().join(format!("wordle-settings-{}", std::process::id()));
+    let path = dir.join("wordle").join("settings.json");
+    // 文件不存在时使用默认设置
+    assert_eq!(Settings::load(&path).unwrap(), Settings::default());
+
+    let mut settings = Settings {
+        hard: true,
+        theme: "light".to_string(),
+        ..Default::default()
+    };
+    settings.next_word_length();
+    assert_eq!(settings.word_length, 5);
+    settings.save(&path).unwrap();
+    assert_eq!(Settings::load(&path).unwrap(), settings);
+
+    // 缺少的字段使用默认值
+    fs::write(&path, r#"{"hard": true}"#).unwrap();
+    let settings = Settings::load(&path).unwrap();
+    assert!(settings.hard);
+    assert_eq!(settings.theme, "dark");
+    // 内置词库不支持的长度换成默认长度
+    fs::write(&path, r#"{"word_length": 7}"#).unwrap();
+    assert_eq!(Settings::load(&path).unwrap().word_length, 5);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_statistics_distribution() {
+    let dir = std::env::temp_dir().join(format!("wordle-stats-{}", std::process::id()));
+    let path = dir.join("state.json");
+    let mut statistics = WordleStatistic::load(&path).unwrap();
+    assert_eq!(statistics.win_rate(), 0.0);
+
+    statistics.record(true, 3);
+    statistics.record(true, 3);
+    statistics.record(true, 6);
+    statistics.record(false, 6);
+    assert_eq!(statistics.total, 4);
+    assert_eq!(statistics.success_total, 3);
+    assert_eq!(statistics.distribution, vec![0, 0, 2, 0, 0, 1]);
+    assert_eq!(statistics.win_rate(), 75.0);
+
+    fs::create_dir_all(&dir).unwrap();
+    statistics.save(&path).unwrap();
+    let loaded = WordleStatistic::load(&path).unwrap();
+    assert_eq!(loaded.distribution, statistics.distribution);
+    assert_eq!(loaded.total, 4);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_settings_screen() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use wordle::{
+        tui::{
+            action::{get_action, update},
+            event::Event,
+            ui::{MainState, UiState},
+        },
+        wordle::Wordle,
+    };
+
+    let mut wordle = Wordle {
+        ui_state: UiState::Main(MainState::Main),
+        ..common::new_wordle("shire")
+    };
+    wordle.start_game().unwrap();
+    let press = |wordle: &mut Wordle, code: KeyCode| {
+        let action = get_action(wordle, Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+        update(wordle, action).unwrap();
+    };
+
+    press(&mut wordle, KeyCode::F(3));
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Settings));
+    // 还没有猜测时立即切换到困难模式
+    press(&mut wordle, KeyCode::Enter);
+    assert!(wordle.settings.hard);
+    assert!(wordle.opt.difficult);
+    press(&mut wordle, KeyCode::Down);
+    press(&mut wordle, KeyCode::Right);
+    assert_eq!(wordle.settings.theme, wordle.theme.name);
+    assert_ne!(wordle.settings.theme, "dark");
+    // 只有一种可选长度, 切换后不变
+    press(&mut wordle, KeyCode::Down);
+    press(&mut wordle, KeyCode::Enter);
+    assert_eq!(wordle.settings.word_length, 5);
+    press(&mut wordle, KeyCode::Down);
+    press(&mut wordle, KeyCode::Enter);
+    assert!(!wordle.settings.animations);
+    press(&mut wordle, KeyCode::Esc);
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Main));
+
+    press(&mut wordle, KeyCode::F(2));
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Stats));
+    press(&mut wordle, KeyCode::Enter);
+    press(&mut wordle, KeyCode::F(1));
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Help));
+    press(&mut wordle, KeyCode::Esc);
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Main));
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn test_cli_overrides_not_saved() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use wordle::{
+        command::Opt,
+        tui::{
+            action::{get_action, update, Action},
+            event::Event,
+            ui::{MainState, UiState},
+        },
+        wordle::Wordle,
+    };
+
+    let dir = std::env::temp_dir().join(format!("wordle-overrides-{}", std::process::id()));
+    let path = dir.join("settings.json");
+    // 相当于`--difficult --no-animation`
+    let mut wordle = Wordle {
+        opt: Opt {
+            difficult: true,
+            no_animation: true,
+            settings: Some(path.clone()),
+            word: Some("shire".to_string()),
+            ..Default::default()
+        },
+        ui_state: UiState::Main(MainState::Main),
+        ..common::new_wordle("shire")
+    };
+    wordle.start_game().unwrap();
+    let press = |wordle: &mut Wordle, code: KeyCode| {
+        let action = get_action(wordle, Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+        update(wordle, action).unwrap();
+    };
+
+    // 修改主题时只保存主题
+    press(&mut wordle, KeyCode::F(3));
+    press(&mut wordle, KeyCode::Down);
+    press(&mut wordle, KeyCode::Enter);
+    let saved = Settings::load(&path).unwrap();
+    assert_eq!(saved.theme, wordle.theme.name);
+    assert!(!saved.hard);
+    assert!(saved.animations);
+    assert!(wordle.opt.difficult);
+
+    // 已有猜测时关闭困难模式, 从下一局开始生效
+    press(&mut wordle, KeyCode::Esc);
+    for code in "crane\n".chars().map(|ch| match ch {
+        '\n' => KeyCode::Enter,
+        ch => KeyCode::Char(ch),
+    }) {
+        press(&mut wordle, code);
+    }
+    assert_eq!(wordle.history_words.len(), 1);
+    press(&mut wordle, KeyCode::F(3));
+    press(&mut wordle, KeyCode::Enter);
+    assert!(wordle.opt.difficult);
+    assert_eq!(wordle.pending_hard, Some(false));
+    update(&mut wordle, Action::ReNew).unwrap();
+    assert!(!wordle.opt.difficult);
+    assert!(!Settings::load(&path).unwrap().hard);
+    fs::remove_dir_all(&dir).unwrap();
+}
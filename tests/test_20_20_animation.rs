@@ -0,0 +1,99 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use ratatui::{backend::TestBackend, Terminal};
+use wordle::{
+    tui::{
+        action::{update, Action},
+        animation::{Animation, AnimationKind, TileFrame},
+        ui::{self, MainState, UiState},
+        widgets::init_keyboard,
+    },
+    wordle::Wordle,
+};
+
+fn new_wordle() -> Wordle {
+    let mut wordle = Wordle {
+        ui_state: UiState::Main(MainState::Main),
+        ..common::new_wordle("shire")
+    };
+    wordle.start_game().unwrap();
+    wordle
+}
+
+fn enter(wordle: &mut Wordle, word: &str) {
+    for ch in word.chars() {
+        update(wordle, Action::InputChar(ch)).unwrap();
+    }
+    update(wordle, Action::Enter).unwrap();
+}
+
+/// 播放动画直到结束并绘制每一帧, 返回经过的帧数
+fn play(wordle: &mut Wordle) -> u32 {
+    let mut terminal = Terminal::new(TestBackend::new(80, 40)).unwrap();
+    let keyboards = init_keyboard();
+    let mut frames = 0;
+    while wordle.animation.is_some() {
+        terminal
+            .draw(|frame| ui::ui(wordle, frame, &keyboards))
+            .unwrap();
+        update(wordle, Action::Render).unwrap();
+        frames += 1;
+    }
+    frames
+}
+
+#[test]
+fn test_reveal_tiles_in_order() {
+    let mut reveal = Animation::reveal(0, false);
+    assert_eq!(reveal.tile(0), TileFrame::Flipping(false));
+    assert_eq!(reveal.tile(1), TileFrame::Hidden);
+    while reveal.tile(0) != TileFrame::Normal {
+        reveal = reveal.advance().unwrap();
+    }
+    assert_eq!(reveal.tile(1), TileFrame::Flipping(false));
+    assert_eq!(reveal.tile(4), TileFrame::Hidden);
+
+    let mut wordle = new_wordle();
+    enter(&mut wordle, "crane");
+    assert_eq!(
+        wordle.animation.map(|a| a.kind),
+        Some(AnimationKind::Reveal)
+    );
+    assert_eq!(play(&mut wordle), Animation::reveal(0, false).frames());
+}
+
+#[test]
+fn test_shake_and_bounce() {
+    let mut wordle = new_wordle();
+    enter(&mut wordle, "zzzzz");
+    assert_eq!(wordle.animation.map(|a| a.kind), Some(AnimationKind::Shake));
+    // 按任意键跳过动画, 按键照常处理
+    update(&mut wordle, Action::RemoveChar).unwrap();
+    assert!(wordle.animation.is_none());
+    assert_eq!(wordle.states.current_word.to_string(), "zzzz");
+    for _ in 0..4 {
+        update(&mut wordle, Action::RemoveChar).unwrap();
+    }
+
+    enter(&mut wordle, "crane");
+    play(&mut wordle);
+    enter(&mut wordle, "shire");
+    let reveal = Animation::reveal(1, true).frames();
+    for _ in 0..reveal {
+        update(&mut wordle, Action::Render).unwrap();
+    }
+    // 猜中后翻开完毕接着跳起
+    assert_eq!(
+        wordle.animation.map(|a| a.kind),
+        Some(AnimationKind::Bounce)
+    );
+    play(&mut wordle);
+
+    // 关闭动画后不再播放
+    let mut wordle = new_wordle();
+    wordle.settings.animations = false;
+    enter(&mut wordle, "crane");
+    assert!(wordle.animation.is_none());
+}
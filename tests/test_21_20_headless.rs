@@ -0,0 +1,413 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use futures::stream;
+use ratatui::{backend::TestBackend, buffer::Buffer, style::Style, Terminal};
+use wordle::{
+    command::Opt,
+    i18n::{self, Locale},
+    pattern::{score, Pattern},
+    state::LetterState,
+    tui::{
+        action::{next_reverse_guess, update, Action},
+        controller::drive,
+        event::Event,
+        ui::{MainState, UiState},
+    },
+    word::Word,
+    wordle::Wordle,
+};
+
+fn new_wordle(opt: Opt) -> Wordle {
+    let mut wordle = Wordle {
+        opt,
+        ..common::new_wordle("shire")
+    };
+    wordle.settings.animations = false;
+    wordle
+}
+
+fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+/// 按键脚本: 字母逐个输入, `\n`为回车
+fn keys(text: &str) -> Vec<Event> {
+    text.chars()
+        .map(|ch| match ch {
+            '\n' => key(KeyCode::Enter),
+            ch => key(KeyCode::Char(ch)),
+        })
+        .collect()
+}
+
+/// 在`width`x`height`的终端中依次发送事件, 最后绘制一帧, 返回去掉样式的缓冲区
+async fn render(wordle: &mut Wordle, mut events: Vec<Event>, width: u16, height: u16) -> Buffer {
+    i18n::set_locale(Locale::En);
+    events.push(Event::Render);
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    drive(wordle, &mut terminal, stream::iter(events))
+        .await
+        .unwrap();
+    let mut buffer = terminal.backend().buffer().clone();
+    buffer.set_style(buffer.area, Style::reset());
+    buffer
+}
+
+/// 依次发送事件, 最后绘制一帧, 返回屏幕内容
+async fn play(wordle: &mut Wordle, events: Vec<Event>) -> String {
+    let buffer = render(wordle, events, 100, 40).await;
+    common::screen_lines(&buffer).join("\n")
+}
+
+/// 等待后台计算的结果送回事件循环
+async fn wait_for(wordle: &mut Wordle, done: impl Fn(&Wordle) -> bool) {
+    for _ in 0..600 {
+        if done(wordle) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        play(wordle, vec![]).await;
+    }
+    panic!("background job did not finish");
+}
+
+#[tokio::test]
+async fn test_init_to_game_over() {
+    let mut wordle = new_wordle(Opt {
+        difficult: true,
+        ..Default::default()
+    });
+    wordle.final_word = Word::default();
+    let text = play(&mut wordle, keys("shi")).await;
+    assert_eq!(wordle.ui_state, UiState::Init);
+    assert!(text.contains("Enter the word to be guessed!"));
+    assert!(text.contains("shi"));
+
+    let text = play(&mut wordle, keys("re\n")).await;
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Main));
+    assert!(text.contains("Wordle"));
+
+    // 困难模式下没有使用已揭示的字母
+    let text = play(&mut wordle, keys("crane\nmoist\n")).await;
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Difficult));
+    assert!(text.contains("Warning"));
+
+    let text = play(&mut wordle, keys("\n")).await;
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Main));
+    assert!(!text.contains("Warning"));
+
+    let mut events = vec![key(KeyCode::Backspace); 5];
+    events.extend(keys("shire\n\n"));
+    let text = play(&mut wordle, events).await;
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::GameOver));
+    assert!(text.contains("Notice"));
+
+    let text = play(&mut wordle, keys("a")).await;
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Review));
+    assert!(text.contains("Review"));
+    // 分析在后台计算, 完成后显示技巧分
+    wait_for(&mut wordle, |wordle| wordle.states.analysis.is_some()).await;
+    assert!(!wordle.states.analysis_pending);
+    let text = play(&mut wordle, vec![]).await;
+    assert!(text.contains("/100"), "{}", text);
+
+    play(&mut wordle, vec![key(KeyCode::Enter), key(KeyCode::Esc)]).await;
+    assert!(wordle.exit);
+}
+
+#[tokio::test]
+async fn test_screens() {
+    let mut wordle = new_wordle(Opt {
+        practice: true,
+        ..Default::default()
+    });
+    let mut events = vec![Event::Init];
+    events.extend(keys("crane\n"));
+    let text = play(&mut wordle, events).await;
+    assert_eq!(wordle.history_words.len(), 1);
+    assert!(text.contains("c   r   a   n   e"));
+
+    for (code, state, title) in [
+        (KeyCode::F(1), MainState::Help, "Help"),
+        (KeyCode::F(2), MainState::Stats, "Statistics"),
+        (KeyCode::F(3), MainState::Settings, "Settings"),
+    ] {
+        let text = play(&mut wordle, vec![key(code)]).await;
+        assert_eq!(wordle.ui_state, UiState::Main(state));
+        assert!(text.contains(title), "{} not on screen", title);
+        play(&mut wordle, vec![key(KeyCode::Esc)]).await;
+        assert_eq!(wordle.ui_state, UiState::Main(MainState::Main));
+    }
+
+    let ctrl_b = Event::Key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL));
+    let text = play(&mut wordle, vec![ctrl_b]).await;
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Branches));
+    assert!(text.contains("Branches"));
+    assert!(!wordle.exit);
+}
+
+#[tokio::test]
+async fn test_assist_and_reverse() {
+    let mut wordle = new_wordle(Opt::default());
+    wordle.ui_state = UiState::Assist;
+    let text = play(&mut wordle, keys("crane1")).await;
+    assert!(text.contains("Assistant"));
+    assert_eq!(wordle.states.current_word.to_string(), "crane");
+
+    // 不在词库中的猜测: 显示错误, 保留输入的单词和颜色
+    let mut wordle = new_wordle(Opt::default());
+    wordle.ui_state = UiState::Assist;
+    play(&mut wordle, keys("zzzzz13")).await;
+    let typed = wordle.states.current_word.clone();
+    let text = play(&mut wordle, keys("\n")).await;
+    assert!(wordle.history_words.is_empty());
+    assert_eq!(wordle.states.current_word, typed);
+    assert!(
+        text.contains("`zzzzz` is not in the acceptable word list"),
+        "{}",
+        text
+    );
+
+    let mut wordle = new_wordle(Opt::default());
+    wordle.ui_state = UiState::Reverse;
+    next_reverse_guess(&mut wordle).unwrap();
+    let text = play(&mut wordle, vec![]).await;
+    assert!(text.contains("Reverse"));
+    assert!(text.contains("Think of a word"));
+
+    play(&mut wordle, vec![key(KeyCode::Esc)]).await;
+    assert!(wordle.exit);
+}
+
+/// 反向模式中把当前行的颜色切换成`target`的按键: 每个数字键把对应字母按 灰 -> 黄 -> 绿 循环
+fn color_keys(wordle: &Wordle, target: Pattern) -> String {
+    let order = |state: LetterState| match state {
+        LetterState::R => 0i32,
+        LetterState::Y => 1,
+        LetterState::G => 2,
+        LetterState::X => -1,
+    };
+    let letters = wordle.states.current_word.get_letters();
+    target
+        .states()
+        .iter()
+        .zip(letters)
+        .enumerate()
+        .flat_map(|(index, (to, letter))| {
+            let presses = (order(*to) - order(letter.1)).rem_euclid(3) as usize;
+            let presses = if letter.1 == LetterState::X && presses == 0 {
+                3
+            } else {
+                presses
+            };
+            std::iter::repeat_n(char::from(b'1' + index as u8), presses)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_reverse_fix_inconsistent_feedback() {
+    let mut wordle = new_wordle(Opt::default());
+    wordle.ui_state = UiState::Reverse;
+    wordle
+        .reverse_feedback("crane", score("crane", "cigar"))
+        .unwrap();
+    next_reverse_guess(&mut wordle).unwrap();
+    let guess = wordle.states.current_word.to_string();
+    // 所有候选词都以绿色的c开头
+    assert!(guess.starts_with('c'), "{}", guess);
+
+    // 把c标成灰色与第一次反馈矛盾: 不记录, 保留猜测和颜色
+    play(&mut wordle, keys("1")).await;
+    let marked = wordle.states.current_word.clone();
+    let text = play(&mut wordle, keys("\n")).await;
+    assert_eq!(wordle.history_words.len(), 1);
+    assert_eq!(wordle.states.current_word, marked);
+    assert_eq!(wordle.states.current_word.to_string(), guess);
+    assert!(
+        text.contains("contradicts the earlier feedback"),
+        "{}",
+        text
+    );
+
+    // 改正颜色后重新提交
+    let colors = color_keys(&wordle, score(&guess, "cigar"));
+    play(&mut wordle, keys(&colors)).await;
+    play(&mut wordle, keys("\n")).await;
+    assert_eq!(wordle.history_words.len(), 2);
+    assert_eq!(wordle.history_words[1].to_string(), guess);
+    assert_eq!(wordle.states.message, None);
+    assert!(wordle.is_game_over() || wordle.states.current_word.is_full());
+}
+
+#[tokio::test]
+async fn test_hint_in_background() {
+    let mut wordle = new_wordle(Opt::default());
+    let mut events = vec![Event::Init];
+    events.extend(keys("crane\n?"));
+    let text = play(&mut wordle, events).await;
+    // 事件循环不等待求解, 先显示正在计算
+    if wordle.states.hint.is_none() {
+        assert!(wordle.states.hint_pending);
+        assert!(text.contains("thinking..."), "{}", text);
+    }
+    wait_for(&mut wordle, |wordle| wordle.states.hint.is_some()).await;
+    assert!(!wordle.states.hint_pending);
+
+    // 计算期间又猜了一次, 旧的结果丢弃
+    let history = wordle.history_words.clone();
+    play(&mut wordle, keys("moist\n")).await;
+    update(
+        &mut wordle,
+        Action::HintReady(history, Some("crane".into())),
+    )
+    .unwrap();
+    assert_eq!(wordle.states.hint, None);
+}
+
+#[tokio::test]
+async fn test_full_screen_snapshots() {
+    let mut wordle = new_wordle(Opt::default());
+    let buffer = render(&mut wordle, vec![Event::Init], 40, 10).await;
+    assert_eq!(
+        buffer,
+        Buffer::with_lines(vec![
+            "                                        ",
+            "                                        ",
+            "                                        ",
+            "                                        ",
+            "Terminal too small: 40x10, need at least",
+            "                  50x29                 ",
+            "                                        ",
+            "                                        ",
+            "                                        ",
+            "                                        ",
+        ])
+    );
+
+    let mut wordle = new_wordle(Opt::default());
+    wordle.final_word = Word::default();
+    let buffer = render(&mut wordle, keys("shi"), 50, 29).await;
+    assert_eq!(
+        buffer,
+        Buffer::with_lines(vec![
+            "┌────────────────────────────────────────────────┐",
+            "│Enter the word to be guessed!                   │",
+            "└────────────────────────────────────────────────┘",
+            "┌────────────────────────────────────────────────┐",
+            "│shi                                             │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "│                                                │",
+            "└────────────────────────────────────────────────┘",
+            "┌────────────────────────────────────────────────┐",
+            "│Status: The word is not set or still being typed│",
+            "└────────────────────────────────────────────────┘",
+        ])
+    );
+
+    // 没有Tick事件, 计时停在00:00
+    let mut wordle = new_wordle(Opt::default());
+    let mut events = vec![Event::Init];
+    events.extend(keys("crane\n"));
+    let buffer = render(&mut wordle, events, 50, 29).await;
+    assert_eq!(
+        buffer,
+        Buffer::with_lines(vec![
+            "┌Wordle─────────────────────────────────── 00:00 ┐",
+            "│┌Guesses───────────────────────────────────────┐│",
+            "││ c   r   a   n   e                            ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "│└──────────────────────────────────────────────┘│",
+            "│┌Keyboard──────────────────────────────────────┐│",
+            "││ Q   W   E   R   T   Y   U   I   O   P        ││",
+            "││                                              ││",
+            "││ A   S   D   F   G   H   J   K   L            ││",
+            "││                                              ││",
+            "││   ⇦    Z   X   C   V   B   N   M   Enter     ││",
+            "││                                              ││",
+            "││                                              ││",
+            "│└──────────────────────────────────────────────┘│",
+            "│-> Status: [Main] Hard: [no], Random: [no] | Gue│",
+            "│                                                │",
+            "│                                                │",
+            "└────────────────────────────────────────────────┘",
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_review_pending_snapshot() {
+    // 后台分析还没有返回时, 复盘窗口显示占位文字
+    let mut wordle = new_wordle(Opt::default());
+    wordle.ui_state = UiState::Main(MainState::Review);
+    wordle.states.analysis_pending = true;
+    let buffer = render(&mut wordle, vec![], 50, 29).await;
+    assert_eq!(
+        buffer,
+        Buffer::with_lines(vec![
+            "┌Wordle─────────────────────────────────── 00:00 ┐",
+            "│┌Guesses───────────────────────────────────────┐│",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││                                              ││",
+            "││   ┌────────────────Review────────────────┐   ││",
+            "││   │Analyzing the game...                 │   ││",
+            "││   │Press <Enter> to go back, <Esc> to qui│   ││",
+            "││   │                                      │   ││",
+            "││   │                                      │   ││",
+            "││   │                                      │   ││",
+            "││   │                                      │   ││",
+            "││   │                                      │   ││",
+            "││   │                                      │   ││",
+            "│└───│                                      │───┘│",
+            "│┌Key│                                      │───┐│",
+            "││ Q │                                      │   ││",
+            "││   │                                      │   ││",
+            "││ A │                                      │   ││",
+            "││   │                                      │   ││",
+            "││   │                                      │   ││",
+            "││   └──────────────────────────────────────┘   ││",
+            "││                                              ││",
+            "│└──────────────────────────────────────────────┘│",
+            "│-> Status: [Review] Hard: [no], Random: [no] | W│",
+            "│                                                │",
+            "│                                                │",
+            "└────────────────────────────────────────────────┘",
+        ])
+    );
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use futures::stream;
+use ratatui::{backend::TestBackend, Terminal};
+use wordle::{
+    i18n::{self, Locale},
+    tui::{
+        clock::Clock,
+        controller::{drive, Stopped},
+        event::Event,
+        ui::{MainState, UiState},
+    },
+};
+
+#[tokio::test]
+async fn test_resize_and_suspend() {
+    i18n::set_locale(Locale::En);
+    let mut wordle = common::new_wordle("shire");
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    let events = vec![Event::Init, Event::Render];
+    let stopped = drive(&mut wordle, &mut terminal, stream::iter(events))
+        .await
+        .unwrap();
+    assert_eq!(stopped, Stopped::EventsEnded);
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Main));
+
+    // 缩小后立即按新大小重绘
+    terminal.backend_mut().resize(40, 12);
+    let events = vec![Event::Resize(40, 12)];
+    drive(&mut wordle, &mut terminal, stream::iter(events))
+        .await
+        .unwrap();
+    assert!(
+        common::screen_lines(terminal.backend().buffer())[5].contains("Terminal too small: 40x12")
+    );
+
+    // 挂起后恢复时的Init不会重新开局
+    let ctrl_z = Event::Key(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL));
+    let events = vec![Event::Key(KeyEvent::from(KeyCode::Char('c'))), ctrl_z];
+    let stopped = drive(&mut wordle, &mut terminal, stream::iter(events))
+        .await
+        .unwrap();
+    assert_eq!(stopped, Stopped::Suspend);
+    assert!(wordle.clock.paused);
+    drive(&mut wordle, &mut terminal, stream::iter(vec![Event::Init]))
+        .await
+        .unwrap();
+    assert_eq!(wordle.states.current_word.to_string(), "c");
+}
+
+#[tokio::test]
+async fn test_clock_pauses_on_focus_lost() {
+    let mut clock = Clock::default();
+    clock.update(true);
+    thread::sleep(Duration::from_millis(20));
+    clock.update(true);
+    assert!(clock.elapsed() >= Duration::from_millis(20));
+
+    // 新的一局从零开始计时
+    let mut wordle = common::new_wordle("shire");
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    let events = vec![Event::Init, Event::FocusLost, Event::Tick];
+    drive(&mut wordle, &mut terminal, stream::iter(events))
+        .await
+        .unwrap();
+    thread::sleep(Duration::from_millis(20));
+    drive(&mut wordle, &mut terminal, stream::iter(vec![Event::Tick]))
+        .await
+        .unwrap();
+    assert!(wordle.clock.paused);
+    assert_eq!(wordle.clock.elapsed(), Duration::ZERO);
+
+    // 获得焦点后继续计时, 暂停期间不计入
+    let events = vec![Event::FocusGained, Event::Tick];
+    drive(&mut wordle, &mut terminal, stream::iter(events))
+        .await
+        .unwrap();
+    thread::sleep(Duration::from_millis(20));
+    drive(&mut wordle, &mut terminal, stream::iter(vec![Event::Tick]))
+        .await
+        .unwrap();
+    assert!(!wordle.clock.paused);
+    assert!(wordle.clock.elapsed() >= Duration::from_millis(20));
+    assert!(wordle.clock.elapsed() < Duration::from_secs(5));
+}
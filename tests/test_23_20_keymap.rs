@@ -0,0 +1,181 @@
+#![cfg(feature = "tui")]
+
+use std::fs;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use wordle::tui::{
+    action::{get_action, Action},
+    event::Event,
+    keymap::{Chord, Command, Keymap, Scope, PRESETS},
+    ui::{MainState, UiState},
+};
+use wordle::wordle::Wordle;
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+#[test]
+fn test_parse_chords() {
+    let chord: Chord = "ctrl-z".parse().unwrap();
+    assert_eq!(
+        chord,
+        Chord::from(key(KeyCode::Char('z'), KeyModifiers::CONTROL))
+    );
+    assert_eq!("F1".parse::<Chord>().unwrap().code, KeyCode::F(1));
+    assert_eq!("ctrl--".parse::<Chord>().unwrap().code, KeyCode::Char('-'));
+    // 字符键忽略shift
+    assert_eq!(
+        "?".parse::<Chord>().unwrap(),
+        Chord::from(key(KeyCode::Char('?'), KeyModifiers::SHIFT))
+    );
+    for text in ["ctrl-alt-x", "enter", "space", "f12", "shift-up"] {
+        assert_eq!(text.parse::<Chord>().unwrap().to_string(), text);
+    }
+    assert!("ctrl-".parse::<Chord>().is_err());
+    assert!("hyper-x".parse::<Chord>().is_err());
+}
+
+#[test]
+fn test_presets_and_conflicts() {
+    for name in PRESETS {
+        assert!(Keymap::preset(name).unwrap().conflicts().is_empty());
+    }
+    let vim = Keymap::preset("vim").unwrap();
+    let ctrl_n = key(KeyCode::Char('n'), KeyModifiers::CONTROL);
+    // 同一按键在不同界面绑定不同命令
+    assert_eq!(vim.command(ctrl_n, Scope::Main), Some(Command::NewGame));
+    assert_eq!(vim.command(ctrl_n, Scope::List), Some(Command::SelectDown));
+
+    let dir = std::env::temp_dir().join(format!("wordle-keymap-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("keymap.json");
+    fs::write(
+        &path,
+        r#"{"preset": "vim", "bindings": {"share": ["f5"], "hint": []}}"#,
+    )
+    .unwrap();
+    let keymap = Keymap::load(&path).unwrap();
+    assert_eq!(keymap.preset, "vim");
+    assert_eq!(keymap.chords(Command::Share), vec!["f5".parse().unwrap()]);
+    assert!(keymap.chords(Command::Hint).is_empty());
+
+    // 与主题切换冲突, 字母挡住输入
+    fs::write(
+        &path,
+        r#"{"bindings": {"share": ["ctrl-t"], "hint": ["h"]}}"#,
+    )
+    .unwrap();
+    let error = Keymap::load(&path).unwrap_err().to_string();
+    assert!(error.contains("`ctrl-t`"), "{}", error);
+    assert!(error.contains("`h`"), "{}", error);
+
+    fs::write(&path, r#"{"bindings": {"fly": ["f6"]}}"#).unwrap();
+    assert!(Keymap::load(&path).is_err());
+    fs::remove_dir_all(&dir).unwrap();
+    // 文件不存在时使用默认预设
+    assert_eq!(Keymap::load(&path).unwrap(), Keymap::default());
+}
+
+#[test]
+fn test_actions_follow_keymap() {
+    let mut wordle = Wordle {
+        ui_state: UiState::Main(MainState::Main),
+        keymap: Keymap::preset("vim").unwrap(),
+        ..Default::default()
+    };
+    let press = |wordle: &Wordle, code: KeyCode, modifiers: KeyModifiers| {
+        get_action(wordle, Event::Key(key(code, modifiers)))
+    };
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('j'), KeyModifiers::NONE),
+        Action::InputChar('j')
+    ));
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('n'), KeyModifiers::CONTROL),
+        Action::NewGame
+    ));
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('h'), KeyModifiers::CONTROL),
+        Action::RemoveChar
+    ));
+    // 没有绑定的ctrl组合不再当作输入
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('q'), KeyModifiers::CONTROL),
+        Action::None
+    ));
+
+    wordle.ui_state = UiState::Main(MainState::Settings);
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('j'), KeyModifiers::NONE),
+        Action::SelectDown
+    ));
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('t'), KeyModifiers::CONTROL),
+        Action::SwitchTheme
+    ));
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('j'), KeyModifiers::CONTROL),
+        Action::ChangeSetting
+    ));
+
+    // 其他界面同样使用绑定的提交键
+    wordle.ui_state = UiState::Main(MainState::Help);
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('j'), KeyModifiers::CONTROL),
+        Action::EnterMain
+    ));
+    wordle.ui_state = UiState::Assist;
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('j'), KeyModifiers::CONTROL),
+        Action::Enter
+    ));
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('h'), KeyModifiers::CONTROL),
+        Action::RemoveChar
+    ));
+}
+
+#[test]
+fn test_rebind_game_over_buttons() {
+    let dir = std::env::temp_dir().join(format!("wordle-popup-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("keymap.json");
+    // 弹窗中没有输入, 复盘可以绑定字母, 输入界面的命令不行
+    fs::write(&path, r#"{"bindings": {"review": ["r"], "share": ["s"]}}"#).unwrap();
+    assert!(Keymap::load(&path).is_err());
+    fs::write(&path, r#"{"bindings": {"review": ["r"]}}"#).unwrap();
+    let keymap = Keymap::load(&path).unwrap();
+    fs::write(&path, r#"{"bindings": {"review": ["ctrl-s"]}}"#).unwrap();
+    let error = Keymap::load(&path).unwrap_err().to_string();
+    assert!(error.contains("`ctrl-s`"), "{}", error);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let mut wordle = Wordle {
+        ui_state: UiState::Main(MainState::GameOver),
+        keymap,
+        ..Default::default()
+    };
+    let press = |wordle: &Wordle, code: KeyCode, modifiers: KeyModifiers| {
+        get_action(wordle, Event::Key(key(code, modifiers)))
+    };
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('r'), KeyModifiers::NONE),
+        Action::Review
+    ));
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('a'), KeyModifiers::NONE),
+        Action::InputChar('a')
+    ));
+    // 弹窗中输入界面的命令同样生效
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('s'), KeyModifiers::CONTROL),
+        Action::Share
+    ));
+    // 弹窗之外复盘键照常输入
+    wordle.ui_state = UiState::Main(MainState::Main);
+    assert!(matches!(
+        press(&wordle, KeyCode::Char('r'), KeyModifiers::NONE),
+        Action::InputChar('r')
+    ));
+}
@@ -0,0 +1,44 @@
+use wordle::layout::{KeyboardLayout, LAYOUTS};
+
+#[test]
+fn test_builtin_layouts() {
+    for (name, _) in LAYOUTS {
+        let mut keys = KeyboardLayout::builtin(name).unwrap().keys();
+        keys.sort_unstable();
+        assert_eq!(keys, ('A'..='Z').collect::<Vec<_>>(), "{}", name);
+    }
+    let default = KeyboardLayout::default();
+    assert_eq!(default.name, "qwerty");
+    assert_eq!(
+        default.keys().into_iter().collect::<String>(),
+        "QWERTYUIOPASDFGHJKLZXCVBNM"
+    );
+    assert_eq!(KeyboardLayout::builtin("AZERTY").unwrap().rows[0][0], 'A');
+    assert!(KeyboardLayout::builtin("workman").is_none());
+}
+
+#[test]
+fn test_generate_and_resolve() {
+    let layout = KeyboardLayout::generate(
+        "auto",
+        "the quick brown fox jumps over the lazy dog".chars(),
+    );
+    assert_eq!(
+        layout.rows.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![9, 9, 8]
+    );
+    assert_eq!(layout.rows[0][0], 'A');
+
+    let words = vec!["crane".to_string(), "façade".to_string()];
+    // 布局之外的字母放在额外的一行
+    let layout = KeyboardLayout::resolve(Some("qwertz"), &words).unwrap();
+    assert_eq!(layout.rows.len(), 4);
+    assert_eq!(layout.rows[3], vec!['Ç']);
+    let auto = KeyboardLayout::resolve(Some("auto"), &words).unwrap();
+    assert_eq!(auto.keys().into_iter().collect::<String>(), "ACDEFNRÇ");
+    assert_eq!(
+        KeyboardLayout::resolve(None, &["crane".to_string()]).unwrap(),
+        KeyboardLayout::default()
+    );
+    assert!(KeyboardLayout::resolve(Some("workman"), &words).is_err());
+}
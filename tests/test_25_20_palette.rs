@@ -0,0 +1,118 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use futures::stream;
+use ratatui::{backend::TestBackend, Terminal};
+use wordle::{
+    i18n::{self, Locale},
+    tui::{
+        action::{get_action, update},
+        controller::drive,
+        event::Event,
+        palette::{Palette, PaletteCommand},
+        ui::{MainState, UiState},
+    },
+    wordle::Wordle,
+};
+
+fn type_keys(wordle: &mut Wordle, keys: &str) {
+    for ch in keys.chars() {
+        let code = match ch {
+            '\n' => KeyCode::Enter,
+            '\t' => KeyCode::Tab,
+            ch => KeyCode::Char(ch),
+        };
+        let action = get_action(wordle, Event::Key(KeyEvent::from(code)));
+        update(wordle, action).unwrap();
+    }
+}
+
+#[test]
+fn test_parse_and_complete() {
+    i18n::set_locale(Locale::En);
+    assert_eq!(
+        " seed 42 ".parse::<PaletteCommand>().unwrap(),
+        PaletteCommand::Seed(42)
+    );
+    assert_eq!(
+        "hard off".parse::<PaletteCommand>().unwrap(),
+        PaletteCommand::Hard(false)
+    );
+    assert_eq!(
+        "undo".parse::<PaletteCommand>().unwrap(),
+        PaletteCommand::Undo
+    );
+    let error = |s: &str| s.parse::<PaletteCommand>().unwrap_err().to_string();
+    assert!(error("fly").ends_with("unknown command `fly`"));
+    assert!(error("hard maybe").ends_with("usage: :hard on|off"));
+    assert!(error("new now").ends_with("usage: :new"));
+    assert!(error("seed x").contains("`x`"));
+
+    let mut palette = Palette {
+        input: "s".to_string(),
+        error: None,
+    };
+    assert_eq!(
+        palette.completions(),
+        vec!["stats", "share", "seed ", "settings"]
+    );
+    palette.input.push('e');
+    palette.complete();
+    assert_eq!(palette.input, "se");
+    palette.input.push('e');
+    palette.complete();
+    assert_eq!(palette.input, "seed ");
+    palette.input = "hard o".to_string();
+    assert_eq!(palette.completions(), vec!["hard on", "hard off"]);
+}
+
+#[tokio::test]
+async fn test_palette_commands() {
+    i18n::set_locale(Locale::En);
+    let mut wordle = common::new_wordle("shire");
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    drive(&mut wordle, &mut terminal, stream::iter(vec![Event::Init]))
+        .await
+        .unwrap();
+
+    // 面板中的字母不会输入到猜测中
+    type_keys(&mut wordle, ":se");
+    assert_eq!(wordle.palette.as_ref().unwrap().input, "se");
+    assert!(wordle.states.current_word.to_string().is_empty());
+    drive(
+        &mut wordle,
+        &mut terminal,
+        stream::iter(vec![Event::Render]),
+    )
+    .await
+    .unwrap();
+    let screen = common::screen_lines(terminal.backend().buffer());
+    assert!(
+        screen
+            .iter()
+            .any(|line| line.contains(":se_  seed settings")),
+        "{:#?}",
+        screen
+    );
+
+    type_keys(&mut wordle, "e\t42\n");
+    assert!(wordle.palette.is_none());
+    assert_eq!(wordle.opt.seed, Some(42));
+    assert!(wordle.opt.random);
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Main));
+
+    // 出错时面板保持打开
+    type_keys(&mut wordle, ":load /nonexistent/game\n");
+    assert!(wordle.palette.as_ref().unwrap().error.is_some());
+    let action = get_action(&wordle, Event::Key(KeyEvent::from(KeyCode::Esc)));
+    update(&mut wordle, action).unwrap();
+    assert!(wordle.palette.is_none());
+    assert!(!wordle.exit);
+
+    type_keys(&mut wordle, ":hard on\n:stats\n");
+    assert!(wordle.settings.hard);
+    assert!(wordle.opt.difficult);
+    assert_eq!(wordle.ui_state, UiState::Main(MainState::Stats));
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "tui")]
+
+mod common;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use futures::stream;
+use ratatui::{backend::TestBackend, Terminal};
+use wordle::{
+    i18n::{self, Locale},
+    settings::{Autocomplete, Settings},
+    tui::{
+        action::{get_action, update},
+        controller::drive,
+        event::Event,
+        ui::completions,
+    },
+    wordle::Wordle,
+};
+
+fn new_wordle(autocomplete: Autocomplete) -> Wordle {
+    Wordle {
+        settings: Settings {
+            autocomplete,
+            ..Default::default()
+        },
+        ..common::new_wordle("shire")
+    }
+}
+
+fn press(wordle: &mut Wordle, code: KeyCode) {
+    let action = get_action(wordle, Event::Key(KeyEvent::from(code)));
+    update(wordle, action).unwrap();
+}
+
+fn type_keys(wordle: &mut Wordle, keys: &str) {
+    for ch in keys.chars() {
+        press(
+            wordle,
+            if ch == '\n' {
+                KeyCode::Enter
+            } else {
+                KeyCode::Char(ch)
+            },
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_completions_follow_feedback() {
+    i18n::set_locale(Locale::En);
+    let mut wordle = new_wordle(Autocomplete::Consistent);
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    drive(&mut wordle, &mut terminal, stream::iter(vec![Event::Init]))
+        .await
+        .unwrap();
+    assert!(completions(&wordle).is_empty());
+
+    type_keys(&mut wordle, "crane\nsh");
+    let words = completions(&wordle);
+    assert!(!words.is_empty());
+    assert!(words.contains(&"shire"));
+    // 与 crane 的反馈一致: 含 r 和 e, 不含 c, a, n
+    assert!(words
+        .iter()
+        .all(|word| word.starts_with("sh") && word.contains('r') && !word.contains('a')));
+
+    // 列出所有以 sh 开头的单词
+    wordle.settings.autocomplete = Autocomplete::All;
+    assert!(completions(&wordle).contains(&"shack"));
+    wordle.settings.autocomplete = Autocomplete::Off;
+    assert!(completions(&wordle).is_empty());
+}
+
+#[tokio::test]
+async fn test_select_and_accept() {
+    i18n::set_locale(Locale::En);
+    let mut wordle = new_wordle(Autocomplete::All);
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    drive(&mut wordle, &mut terminal, stream::iter(vec![Event::Init]))
+        .await
+        .unwrap();
+    type_keys(&mut wordle, "cran");
+    let words = completions(&wordle)
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    assert_eq!(words[0], "crane");
+
+    // 上键从最后一个开始, tab 回到第一个
+    press(&mut wordle, KeyCode::Up);
+    assert_eq!(wordle.states.completion, Some(words.len() - 1));
+    press(&mut wordle, KeyCode::Tab);
+    assert_eq!(wordle.states.completion, Some(0));
+    press(&mut wordle, KeyCode::Down);
+    drive(
+        &mut wordle,
+        &mut terminal,
+        stream::iter(vec![Event::Render]),
+    )
+    .await
+    .unwrap();
+    let screen = common::screen_lines(terminal.backend().buffer());
+    assert!(
+        screen.iter().any(|line| line.contains(&words[1])),
+        "{:#?}",
+        screen
+    );
+
+    // esc 只取消选择, 不退出
+    press(&mut wordle, KeyCode::Esc);
+    assert_eq!(wordle.states.completion, None);
+    assert!(!wordle.exit);
+
+    press(&mut wordle, KeyCode::Tab);
+    press(&mut wordle, KeyCode::Enter);
+    assert_eq!(wordle.states.current_word.to_string(), "crane");
+    assert!(wordle.history_words.is_empty());
+    press(&mut wordle, KeyCode::Enter);
+    assert_eq!(wordle.history_words.len(), 1);
+}